@@ -17,14 +17,10 @@ set -x
 export RUST_LOG=info 
 export RUST_BACKTRACE=1
 
-## Handle PRs for NuttX Kernel and Apps every 10 minutes
-for (( ; ; ))
-do
-    ## For NuttX Kernel Repo
-    cargo run -- --owner apache --repo nuttx
-    sleep 300
-
-    ## For NuttX Apps Repo
-    cargo run -- --owner apache --repo nuttx-apps
-    sleep 300
-done
+## Handle PRs for NuttX Kernel and Apps every 10 minutes:
+## Build once, then run as Daemon until SIGTERM or Ctrl-C.
+## Repos and Interval are configured in `nuttx-pr-bot.toml`.
+cargo build --release
+./target/release/nuttx-pr-bot \
+    --config nuttx-pr-bot.toml \
+    daemon
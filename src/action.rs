@@ -0,0 +1,64 @@
+//! GitHub Actions Mode: Review the PR in the Workflow Event.
+//!   Read the Event Payload from `GITHUB_EVENT_PATH` (`pull_request` or `pull_request_target`)
+//!   and the Repo from `GITHUB_REPOSITORY`.
+//!   PR is reviewed as a PR Event: Not skipped if the PR Size Label is missing.
+//!   State File on the Runner is discarded, so the retries should be tracked with PR Reactions.
+//!   PR Reactions are created by `github-actions[bot]` (`GITHUB_TOKEN`), unless the Config File sets the Bot Login.
+//! See https://docs.github.com/en/actions/writing-workflows/choosing-what-your-workflow-does/store-information-in-variables#default-environment-variables
+
+use std::{env, fs};
+use serde::Deserialize;
+
+/// Login of the Bot that creates the PR Reactions with `GITHUB_TOKEN`
+pub const BOT_LOGIN: &str = "github-actions[bot]";
+
+/// PR in the GitHub Actions Event
+#[derive(Debug, Clone)]
+pub struct ActionEvent {
+    /// Owner of the Repo (`apache`)
+    pub owner: String,
+
+    /// Name of the Repo (`nuttx`)
+    pub repo: String,
+
+    /// PR Number
+    pub pr_id: u64,
+}
+
+/// Payload of the `pull_request` or `pull_request_target` Event
+#[derive(Deserialize)]
+struct EventPayload {
+    pull_request: Option<EventPull>,
+}
+
+/// Pull Request in the Event Payload
+#[derive(Deserialize)]
+struct EventPull {
+    number: u64,
+}
+
+/// Read the Repo from `GITHUB_REPOSITORY` and the PR Number from the Event Payload at `GITHUB_EVENT_PATH`
+pub fn read_event() -> Result<ActionEvent, Box<dyn std::error::Error>> {
+    // Get the Repo: `apache/nuttx`
+    let repository = env::var("GITHUB_REPOSITORY")
+        .map_err(|_| "GITHUB_REPOSITORY env variable is required")?;
+    let (owner, repo) = repository
+        .split_once('/')
+        .ok_or(format!("GITHUB_REPOSITORY should be `owner/repo`: {}", repository))?;
+
+    // Get the PR Number from the Event Payload
+    let event_path = env::var("GITHUB_EVENT_PATH")
+        .map_err(|_| "GITHUB_EVENT_PATH env variable is required")?;
+    let json = fs::read_to_string(&event_path)
+        .map_err(|e| format!("Unable to read Event {}: {}", event_path, e))?;
+    let payload: EventPayload = serde_json::from_str(&json)
+        .map_err(|e| format!("Invalid Event {}: {}", event_path, e))?;
+    let pull = payload.pull_request
+        .ok_or("Event doesn't contain a Pull Request. Trigger the Workflow with `pull_request` or `pull_request_target`.")?;
+
+    Ok(ActionEvent {
+        owner: owner.to_string(),
+        repo: repo.to_string(),
+        pr_id: pull.number,
+    })
+}
@@ -0,0 +1,84 @@
+//! Check Run and Commit Status for the PR Review, shown next to the CI Checks:
+//!   Conclusion is Success, Neutral or Failure, depending on the Prechecks and the LLM Verdict.
+//!   Check Run Summary contains the Prechecks and the LLM Verdict.
+//!   Check Run has no Annotations: The Prechecks and Verdict are about the PR Body and Commits,
+//!   not the lines of code, so they are reported in the Summary instead.
+//!   Commit Status (`nuttx-pr-bot/template`) is Success or Failure, for Branch Protection.
+
+use log::info;
+use crate::{
+    config::Settings,
+    forge::{CheckRun, CommitStatus, Conclusion, Forge, PullRequest, StatusState},
+    verdict::{self, Outcome, Verdict},
+};
+
+/// Return the Conclusion for the Prechecks and the LLM Verdict (None if there's no LLM):
+/// Failure if the Verdict fails. Neutral if the Verdict passes but the Prechecks don't.
+pub fn conclusion(precheck: &str, verdict: Option<&Verdict>) -> Conclusion {
+    match verdict.map(|v| v.verdict) {
+        Some(Outcome::Fail) => Conclusion::Failure,
+        Some(Outcome::Pass) if !precheck.is_empty() => Conclusion::Neutral,
+        Some(Outcome::Pass) => Conclusion::Success,
+        None if !precheck.is_empty() => Conclusion::Failure,
+        None => Conclusion::Success,
+    }
+}
+
+/// Publish the Check Run for the Prechecks and the LLM Verdict on the PR Head Commit, if enabled
+pub async fn publish_check_run(forge: &dyn Forge, settings: &Settings, pr: &PullRequest, precheck: &str, verdict: Option<&Verdict>) -> Result<(), Box<dyn std::error::Error>> {
+    if !settings.check_run {
+        return Ok(());
+    }
+
+    // Get the Conclusion (Success, Neutral or Failure), which decides the Title
+    let conclusion = conclusion(precheck, verdict);
+
+    // Compose the Summary: Prechecks + LLM Verdict
+    let mut summary =
+        if precheck.is_empty() { "__PR Template:__ This PR contains the Summary, Impact and Testing sections. Thank you!\n\n".to_string() }
+        else { precheck.to_string() };
+    if let Some(verdict) = verdict {
+        summary.push_str(&verdict::render_verdict(verdict, precheck));
+    }
+
+    // Create the Check Run
+    let check = CheckRun {
+        name: settings.check_name.clone(),
+        head_sha: pr.head_sha.clone(),
+        conclusion,
+        title: title(conclusion).to_string(),
+        summary,
+    };
+    info!("Check Run: {:?} {:?}", check.name, check.conclusion);
+    forge.create_check_run(&check).await
+}
+
+/// Publish the Commit Status for the Prechecks and the LLM Verdict on the PR Head Commit, if enabled.
+/// Neutral becomes Success, since the Verdict passes. Commit Status will link to the PR Comment.
+pub async fn publish_status(forge: &dyn Forge, settings: &Settings, pr: &PullRequest, precheck: &str, verdict: Option<&Verdict>, comment_url: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if !settings.commit_status {
+        return Ok(());
+    }
+    let conclusion = conclusion(precheck, verdict);
+    let status = CommitStatus {
+        sha: pr.head_sha.clone(),
+        state: match conclusion {
+            Conclusion::Success | Conclusion::Neutral => StatusState::Success,
+            Conclusion::Failure => StatusState::Failure,
+        },
+        context: settings.status_context.clone(),
+        description: title(conclusion).to_string(),
+        target_url: Some(comment_url.to_string()),
+    };
+    info!("Commit Status: {:?} {:?}", status.context, status.state);
+    forge.create_status(&status).await
+}
+
+/// Return the Title for the Conclusion (up to 140 chars for Commit Status)
+fn title(conclusion: Conclusion) -> &'static str {
+    match conclusion {
+        Conclusion::Success => "PR meets the NuttX Requirements",
+        Conclusion::Neutral => "PR meets the NuttX Requirements, please check the Prechecks",
+        Conclusion::Failure => "PR doesn't meet the NuttX Requirements yet",
+    }
+}
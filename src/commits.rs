@@ -0,0 +1,580 @@
+//! Rule-based Linter for the Commit Messages in the PR:
+//!   Commit Title should be `subsystem: description`, not too long,
+//!   followed by a Blank Line and the Commit Body (wrapped) with `Signed-off-by:`.
+//!   `fixup!`, `squash!` and Merge Commits should be squashed or dropped.
+//!   Subsystem in the Commit Title (`arch/risc-v:`) should match the Paths changed by the Commit.
+//!   Multiple Commits should be squashed, unless each Commit changes a different Subsystem
+//!   or the PR has the `multi-commit` Label.
+//! Each Violation is reported with the Commit SHA. Rules are configurable (`[commits]`).
+
+use std::{cmp::Reverse, collections::BTreeMap};
+use log::info;
+use serde::Deserialize;
+use crate::forge::{ChangedFile, Commit, Forge};
+
+/// Titles of Merge Commits created by Git and GitHub
+const MERGE_TITLES: [&str; 4] = ["Merge branch ", "Merge pull request ", "Merge remote-tracking branch ", "Merge tag "];
+
+/// Top-Level Directories whose Subsystem is 2 levels deep, like `arch/risc-v` and `drivers/i2c`
+const NESTED_DIRS: [&str; 14] = [
+    "arch", "boards", "drivers", "fs", "net", "libs", "include", "Documentation", "tools",
+    "examples", "system", "netutils", "testing", "apps",
+];
+
+/// Top-Level Directories whose Subsystem has a different name, like `CI:` for `.github`
+const DIR_ALIASES: [(&str, &str); 1] = [(".github", "CI")];
+
+/// Rules for the Commit Messages. Zero Length means the Length is not checked.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CommitRules {
+    /// Commit Message should have a Body below the Title
+    pub require_body: bool,
+
+    /// Commit Title should be `subsystem: description`
+    pub title_format: bool,
+
+    /// Subsystem in the Commit Title should match the Paths changed by the Commit
+    pub title_prefix: bool,
+
+    /// Max Length of the Commit Title
+    pub max_title_length: usize,
+
+    /// Second Line of the Commit Message should be blank
+    pub blank_second_line: bool,
+
+    /// Max Width of the Commit Body. Lines without spaces (URLs) are not checked.
+    pub max_body_width: usize,
+
+    /// Commit Message should contain `Signed-off-by:`
+    pub signed_off_by: bool,
+
+    /// PR should not contain `fixup!` or `squash!` Commits
+    pub no_fixup: bool,
+
+    /// PR should not contain Merge Commits
+    pub no_merge: bool,
+
+    /// PR should not contain more than `max_commits` Commits
+    pub squash: bool,
+
+    /// Max Number of Commits before asking to Squash the Commits (at least 1)
+    pub max_commits: usize,
+
+    /// Allow Multiple Commits if each Commit changes a different Subsystem
+    pub allow_subsystems: bool,
+
+    /// Labels that allow Multiple Commits, like `multi-commit`
+    pub multi_commit_labels: Vec<String>,
+}
+
+impl Default for CommitRules {
+    /// Default Rules: Only the Empty Commit Message is checked, same as the NuttX Bot.
+    /// The other Rules are enabled in the Config File.
+    fn default() -> Self {
+        Self {
+            require_body: true,
+            title_format: false,
+            title_prefix: false,
+            max_title_length: 0,
+            blank_second_line: false,
+            max_body_width: 0,
+            signed_off_by: false,
+            no_fixup: false,
+            no_merge: false,
+            squash: false,
+            max_commits: 1,
+            allow_subsystems: true,
+            multi_commit_labels: vec!["multi-commit".to_string()],
+        }
+    }
+}
+
+/// Fetch the Files changed by each Commit, if the Title Prefix or the Subsystems will be checked.
+/// For a Single Commit: The Commit Files are the same as the PR Files.
+pub async fn fetch_commit_files(forge: &dyn Forge, rules: &CommitRules, commits: &[Commit], pr_files: &[ChangedFile]) ->
+    Result<BTreeMap<String, Vec<ChangedFile>>, Box<dyn std::error::Error>> {
+    let mut commit_files = BTreeMap::new();
+    let check_subsystems = rules.squash && rules.allow_subsystems && commits.len() > rules.max_commits;
+    if !rules.title_prefix && !check_subsystems {
+        return Ok(commit_files);
+    }
+    if let [commit] = commits {
+        commit_files.insert(commit.sha.clone(), pr_files.to_vec());
+        return Ok(commit_files);
+    }
+    for commit in commits.iter() {
+        let files = forge
+            .list_commit_files(&commit.sha)
+            .await?;
+        commit_files.insert(commit.sha.clone(), files);
+    }
+    Ok(commit_files)
+}
+
+/// Check the Commit Messages in the PR, and the Title Prefix against the Commit Files (if fetched).
+/// Return the Precheck for the PR Comment (empty if all Commits are OK).
+pub fn check_commits(rules: &CommitRules, commits: &[Commit], commit_files: &BTreeMap<String, Vec<ChangedFile>>) -> String {
+    // Lint each Commit and report the Violations by Commit SHA
+    let mut report = String::new();
+    for commit in commits.iter() {
+        let sha = short_sha(&commit.sha);
+        let mut violations = lint_commit(rules, &commit.message);
+        if let Some(files) = commit_files.get(&commit.sha) {
+            violations.extend(check_prefix(rules, &commit.message, files));
+        }
+        for violation in violations {
+            info!("Commit {}: {}", sha, violation);
+            report.push_str(&format!("* `{sha}`: {violation}\n"));
+        }
+    }
+    if report.is_empty() {
+        return report;
+    }
+    format!("__Fix The Commit Messages:__ Please fix the following Commits, then force-push the PR:\n{report}\n")
+}
+
+/// Check for Multiple Commits that should be squashed. Return the Precheck for the PR Comment
+/// (empty if the Commits are OK), listing each Commit with its Subsystem.
+pub fn check_squash(rules: &CommitRules, commits: &[Commit], commit_files: &BTreeMap<String, Vec<ChangedFile>>, labels: &[String]) -> String {
+    // Skip if the PR has a few Commits, or a Multi-Commit Label
+    if !rules.squash || commits.len() <= rules.max_commits {
+        return String::new();
+    }
+    if let Some(label) = labels.iter().find(|l| rules.multi_commit_labels.contains(l)) {
+        info!("Allowing Multiple Commits for Label: {}", label);
+        return String::new();
+    }
+
+    // Get the Subsystem changed by each Commit: From the Commit Files, or the Commit Title (if it's `subsystem: description`)
+    let subsystems: Vec<String> = commits
+        .iter()
+        .map(|c| commit_files
+            .get(&c.sha)
+            .and_then(|f| subsystems(f).into_iter().next())
+            .or_else(|| title_subsystem(&c.message))
+            .unwrap_or_default())
+        .collect();
+
+    // Skip if each Commit changes a different Subsystem
+    let distinct = subsystems
+        .iter()
+        .enumerate()
+        .all(|(i, s)| !s.is_empty() && !subsystems[..i].contains(s));
+    if rules.allow_subsystems && distinct {
+        info!("Allowing Multiple Commits for Subsystems: {:?}", subsystems);
+        return String::new();
+    }
+
+    // Report each Commit and its Subsystem. Flag the Commits that change the same Subsystem as an earlier Commit.
+    let mut report = String::new();
+    for (i, commit) in commits.iter().enumerate() {
+        let title = commit.message.lines().next().unwrap_or("").trim();
+        let subsystem = &subsystems[i];
+        let mut line = format!("* `{}`: {}", short_sha(&commit.sha), title);
+        if !subsystem.is_empty() {
+            line.push_str(&format!(" (`{subsystem}`)"));
+        }
+        if rules.allow_subsystems {
+            if let Some(j) = subsystems[..i].iter().position(|s| !s.is_empty() && s == subsystem) {
+                line.push_str(&format!(", same Subsystem as `{}`", short_sha(&commits[j].sha)));
+            }
+        }
+        report.push_str(&line);
+        report.push('\n');
+    }
+    let allowed = if rules.max_commits > 1 { format!("{} Commits", rules.max_commits) } else { "a Single Commit".to_string() };
+    let mut precheck = format!("__Squash The Commits:__ This PR contains {} Commits. Please Squash the Multiple Commits into {}", commits.len(), allowed);
+    if rules.allow_subsystems {
+        precheck.push_str(", or One Commit per Subsystem");
+    }
+    if let Some(label) = rules.multi_commit_labels.first() {
+        precheck.push_str(&format!(". To keep the Commits, please ask a Maintainer to add the `{label}` label"));
+    }
+    precheck.push_str(&format!(":\n{report}\n"));
+    precheck
+}
+
+/// Lint the Commit Message. Return the Violations.
+fn lint_commit(rules: &CommitRules, message: &str) -> Vec<String> {
+    // Message should be "title\n\nbody"
+    let message = message.trim_end();
+    let mut lines = message.lines();
+    let title = lines.next().unwrap_or("").trim();
+    let second = lines.next();
+    let body: Vec<&str> = lines.collect();
+    let mut violations: Vec<String> = vec![];
+
+    // Check for fixup! and squash! Commits
+    if rules.no_fixup && (title.starts_with("fixup!") || title.starts_with("squash!")) {
+        violations.push("This is a `fixup!` or `squash!` Commit. Please squash it into the Commit that it fixes.".to_string());
+        return violations;
+    }
+
+    // Check for Merge Commits
+    if rules.no_merge && MERGE_TITLES.iter().any(|t| title.starts_with(t)) {
+        violations.push("This is a Merge Commit. Please rebase the PR instead of merging.".to_string());
+        return violations;
+    }
+
+    // Check for Empty Commit Message
+    if rules.require_body && second.into_iter().chain(body.iter().copied()).all(|l| l.trim().is_empty()) {
+        violations.push("Commit Message is empty. Please fill in the Commit Message with the PR Summary.".to_string());
+    }
+
+    // Check the Title Format: `subsystem: description`
+    if rules.title_format && !is_title_format(title) {
+        violations.push(format!("Commit Title should be `subsystem: description`, like `arch/risc-v: Fix the build`, not `{title}`."));
+    }
+
+    // Check the Title Length
+    let title_length = title.chars().count();
+    if rules.max_title_length > 0 && title_length > rules.max_title_length {
+        violations.push(format!("Commit Title has {} characters. Please shorten it to {} characters.", title_length, rules.max_title_length));
+    }
+
+    // Check for Blank Second Line
+    if rules.blank_second_line && second.is_some_and(|l| !l.trim().is_empty()) {
+        violations.push("Second Line of the Commit Message should be blank.".to_string());
+    }
+
+    // Check the Body Width. Skip the Lines without spaces, like URLs.
+    if rules.max_body_width > 0 {
+        let long_lines = body
+            .iter()
+            .filter(|l| l.chars().count() > rules.max_body_width && l.trim().contains(' '))
+            .count();
+        if long_lines > 0 {
+            violations.push(format!("Commit Message has {} line(s) longer than {} characters. Please wrap the lines.", long_lines, rules.max_body_width));
+        }
+    }
+
+    // Check for Signed-off-by
+    if rules.signed_off_by && !body.iter().any(|l| l.trim_start().starts_with("Signed-off-by:")) {
+        violations.push("Commit Message should end with `Signed-off-by:`. Please commit with `git commit -s`.".to_string());
+    }
+    violations
+}
+
+/// Check that the Subsystem in the Commit Title matches the Paths changed by the Commit.
+/// Return the Violation with the suggested Subsystem.
+fn check_prefix(rules: &CommitRules, message: &str, files: &[ChangedFile]) -> Option<String> {
+    // Skip if the Title Prefix isn't checked, or we don't know the Commit Files, or the Title Format is incorrect
+    let title = message.lines().next().unwrap_or("").trim();
+    if !rules.title_prefix || files.is_empty() || !is_title_format(title) {
+        return None;
+    }
+    let (prefix, _) = title.split_once(": ")?;
+
+    // Prefix is OK if any of its Subsystems matches any Path
+    let matched = prefix
+        .split(',')
+        .any(|s| files.iter().any(|f| prefix_matches(s, &f.path)));
+    if matched {
+        return None;
+    }
+
+    // Suggest the Subsystems changed by the Commit, most files first
+    let subsystems = subsystems(files);
+    let changed = subsystems
+        .iter()
+        .take(3)
+        .map(|s| format!("`{s}`"))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let suggestion = &subsystems[0];
+    Some(format!("Commit Title starts with `{prefix}:`, but the Commit changes {changed}. Please start the Commit Title with `{suggestion}:`."))
+}
+
+/// Return true if the Subsystem (`arch/risc-v` or `esp32c3`) matches the Path.
+/// Each part of the Subsystem should appear in the Path in order, ignoring case, the Leading Dot and the File Extension.
+/// Or the Subsystem is the Alias of the Top-Level Directory, like `CI` for `.github`.
+fn prefix_matches(subsystem: &str, path: &str) -> bool {
+    let subsystem = subsystem.trim().trim_matches('/').to_lowercase();
+    let path = path.to_lowercase();
+    let top = path.split('/').next().unwrap_or("");
+    if DIR_ALIASES.iter().any(|(dir, alias)| *dir == top && alias.to_lowercase() == subsystem) {
+        return true;
+    }
+    let mut parts = path
+        .split('/')
+        .map(|p| p.trim_start_matches('.'))
+        .map(|p| p.split_once('.').map(|(stem, _)| stem).unwrap_or(p));
+    !subsystem.is_empty() &&
+        subsystem
+            .split('/')
+            .all(|s| parts.any(|p| p == s))
+}
+
+/// Return the Subsystems changed by the Files, most files first
+pub fn subsystems(files: &[ChangedFile]) -> Vec<String> {
+    let mut counts: Vec<(String, usize)> = vec![];
+    for file in files.iter() {
+        let subsystem = subsystem(&file.path);
+        match counts.iter_mut().find(|(s, _)| *s == subsystem) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((subsystem, 1)),
+        }
+    }
+    counts.sort_by_key(|(_, count)| Reverse(*count));
+    counts.into_iter().map(|(s, _)| s).collect()
+}
+
+/// Return the Subsystem for the Path: `arch/risc-v/src/esp32c3/esp32c3_irq.c` becomes `arch/risc-v`,
+/// `sched/task/task_create.c` becomes `sched`, `.github/workflows/build.yml` becomes `CI`
+fn subsystem(path: &str) -> String {
+    let parts: Vec<&str> = path.split('/').collect();
+    if let Some((_, alias)) = DIR_ALIASES.iter().find(|(dir, _)| parts.len() > 1 && *dir == parts[0]) {
+        return alias.to_string();
+    }
+    match parts.as_slice() {
+        [top, sub, _, ..] if NESTED_DIRS.contains(top) => format!("{top}/{sub}"),
+        [top, _, ..] => top.to_string(),
+        _ => path.to_string(),
+    }
+}
+
+/// Return the Subsystem in the Commit Title, like `arch/risc-v` for `arch/risc-v: Fix the build`.
+/// None if the Title isn't `subsystem: description`.
+fn title_subsystem(message: &str) -> Option<String> {
+    let title = message.lines().next().unwrap_or("").trim();
+    if !is_title_format(title) {
+        return None;
+    }
+    title.split_once(": ").map(|(subsystem, _)| subsystem.trim().to_string())
+}
+
+/// Return true if the Title is `subsystem: description`.
+/// Subsystem may be a Path (`arch/risc-v`) or a List (`arch/arm, boards`).
+fn is_title_format(title: &str) -> bool {
+    let Some((subsystem, description)) = title.split_once(": ") else {
+        return false;
+    };
+    !description.trim().is_empty() &&
+        subsystem
+            .split(',')
+            .all(|s| !s.trim().is_empty() && !s.trim().contains(char::is_whitespace))
+}
+
+/// Return the Short Commit SHA (7 chars)
+pub fn short_sha(sha: &str) -> &str {
+    sha.get(..7).unwrap_or(sha)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rules with every Check enabled, like the Sample Config File
+    fn strict_rules() -> CommitRules {
+        CommitRules {
+            require_body: true,
+            title_format: true,
+            title_prefix: true,
+            max_title_length: 80,
+            blank_second_line: true,
+            max_body_width: 80,
+            signed_off_by: true,
+            no_fixup: true,
+            no_merge: true,
+            squash: true,
+            ..Default::default()
+        }
+    }
+
+    /// Create the Commit with the SHA and Message
+    fn commit(sha: &str, message: &str) -> Commit {
+        Commit { sha: sha.to_string(), message: message.to_string() }
+    }
+
+    /// Create the Changed Files for the Paths
+    fn files(paths: &[&str]) -> Vec<ChangedFile> {
+        paths
+            .iter()
+            .map(|p| ChangedFile { path: p.to_string(), ..Default::default() })
+            .collect()
+    }
+
+    #[test]
+    fn lint() {
+        let long_title = format!("arch/risc-v: {}", "x".repeat(80));
+        let long_body = format!("arch: Fix\n\n{}\n\nSigned-off-by: A <a@b>", "word ".repeat(20));
+        let url_body = format!("arch: Fix\n\nhttps://example.com/{}\n\nSigned-off-by: A <a@b>", "x".repeat(80));
+        let cases: [(&str, &[&str]); 12] = [
+            ("arch/risc-v: Fix the build\n\nBody text.\n\nSigned-off-by: A <a@b>", &[]),
+            ("arch/risc-v: Fix the build", &["Commit Message is empty.", "Commit Message should end with `Signed-off-by:`."]),
+            ("arch/risc-v: Fix the build\n\n\n", &["Commit Message is empty.", "Commit Message should end with `Signed-off-by:`."]),
+            ("Fix the build\n\nBody text.\n\nSigned-off-by: A <a@b>", &["Commit Title should be `subsystem: description`"]),
+            ("arch/risc-v: Fix\nBody text.\n\nSigned-off-by: A <a@b>", &["Second Line of the Commit Message should be blank."]),
+            (&long_title, &["Commit Message is empty.", "Commit Title has 93 characters.", "Commit Message should end"]),
+            (&long_body, &["Commit Message has 1 line(s) longer than 80 characters."]),
+            (&url_body, &[]),
+            ("fixup! arch/risc-v: Fix the build", &["This is a `fixup!` or `squash!` Commit."]),
+            ("squash! arch/risc-v: Fix the build", &["This is a `fixup!` or `squash!` Commit."]),
+            ("Merge branch 'master' into fix", &["This is a Merge Commit."]),
+            ("Merge pull request #1 from a/b\n\nBody", &["This is a Merge Commit."]),
+        ];
+        for (message, expected) in cases {
+            let violations = lint_commit(&strict_rules(), message);
+            assert_eq!(violations.len(), expected.len(), "{:?}: {:?}", message, violations);
+            for (violation, expected) in violations.iter().zip(expected) {
+                assert!(violation.starts_with(expected), "{:?}: {:?}", message, violation);
+            }
+        }
+    }
+
+    #[test]
+    fn lint_default_rules() {
+        // Default Rules check only the Empty Commit Message
+        let rules = CommitRules::default();
+        assert!(lint_commit(&rules, "Fix the build\nBody text").is_empty());
+        assert!(lint_commit(&rules, "fixup! Fix the build\n\nBody text").is_empty());
+        assert_eq!(lint_commit(&rules, "arch/risc-v: Fix the build").len(), 1);
+    }
+
+    #[test]
+    fn title_format() {
+        let cases = [
+            ("arch/risc-v: Fix the build", true),
+            ("arch/arm, boards: Fix the build", true),
+            ("CI: Update the workflow", true),
+            ("Fix the build", false),
+            ("arch/risc-v:Fix the build", false),
+            ("arch/risc-v: ", false),
+            ("arch risc-v: Fix the build", false),
+            ("arch/arm,: Fix the build", false),
+        ];
+        for (title, expected) in cases {
+            assert_eq!(is_title_format(title), expected, "{:?}", title);
+        }
+    }
+
+    #[test]
+    fn prefix() {
+        let cases = [
+            ("arch/risc-v", "arch/risc-v/src/esp32c3/esp32c3_irq.c", true),
+            ("esp32c3", "arch/risc-v/src/esp32c3/esp32c3_irq.c", true),
+            ("risc-v/esp32c3", "arch/risc-v/src/esp32c3/esp32c3_irq.c", true),
+            ("esp32c3/risc-v", "arch/risc-v/src/esp32c3/esp32c3_irq.c", false),
+            ("libc/stdio", "libs/libc/stdio/lib_printf.c", true),
+            ("lib_printf", "libs/libc/stdio/lib_printf.c", true),
+            ("Drivers/I2C", "drivers/i2c/i2c_driver.c", true),
+            ("drivers/spi", "drivers/i2c/i2c_driver.c", false),
+            ("CI", ".github/workflows/build.yml", true),
+            ("ci", ".github/workflows/build.yml", true),
+            ("github", ".github/workflows/build.yml", true),
+            ("CI", "tools/ci/cibuild.sh", true),
+            ("CI", "arch/risc-v/Kconfig", false),
+            ("", ".github/workflows/build.yml", false),
+            ("mm", "mm/mm_heap/mm_malloc.c", true),
+        ];
+        for (subsystem, path, expected) in cases {
+            assert_eq!(prefix_matches(subsystem, path), expected, "{:?} {:?}", subsystem, path);
+        }
+    }
+
+    #[test]
+    fn check_prefix_suggestion() {
+        let rules = strict_rules();
+        let message = "sched: Fix the build\n\nBody text.\n\nSigned-off-by: A <a@b>";
+        let changed = files(&["mm/mm_heap/mm_malloc.c", "mm/mm_heap/mm_free.c", "sched/init/nx_start.c"]);
+        assert_eq!(check_prefix(&rules, message, &changed), None);
+
+        let changed = files(&["drivers/i2c/i2c_driver.c", "drivers/i2c/i2c_write.c", ".github/workflows/build.yml"]);
+        assert_eq!(
+            check_prefix(&rules, message, &changed).unwrap(),
+            "Commit Title starts with `sched:`, but the Commit changes `drivers/i2c`, `CI`. Please start the Commit Title with `drivers/i2c:`."
+        );
+
+        // Skip if the Commit Files are unknown, or the Title Prefix isn't checked
+        assert_eq!(check_prefix(&rules, message, &[]), None);
+        assert_eq!(check_prefix(&CommitRules::default(), message, &changed), None);
+    }
+
+    #[test]
+    fn subsystems_of_paths() {
+        let cases = [
+            ("arch/risc-v/src/esp32c3/esp32c3_irq.c", "arch/risc-v"),
+            ("libs/libc/stdio/lib_printf.c", "libs/libc"),
+            ("drivers/i2c/i2c_driver.c", "drivers/i2c"),
+            ("drivers/Kconfig", "drivers"),
+            ("sched/task/task_create.c", "sched"),
+            (".github/workflows/build.yml", "CI"),
+            (".github", ".github"),
+            ("tools/ci/cibuild.sh", "tools/ci"),
+            ("Makefile", "Makefile"),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(subsystem(path), expected, "{:?}", path);
+        }
+        assert_eq!(
+            subsystems(&files(&["sched/a.c", "mm/a.c", "mm/b.c"])),
+            vec!["mm".to_string(), "sched".to_string()]
+        );
+    }
+
+    #[test]
+    fn squash() {
+        let rules = strict_rules();
+        let commits = [
+            commit("1111111aaaa", "arch/risc-v: Fix the build"),
+            commit("2222222bbbb", "drivers/i2c: Fix the driver"),
+            commit("3333333cccc", "esp32c3: Fix the IRQ"),
+        ];
+        let mut commit_files = BTreeMap::new();
+        commit_files.insert("1111111aaaa".to_string(), files(&["arch/risc-v/src/common/riscv_exit.c"]));
+        commit_files.insert("2222222bbbb".to_string(), files(&["drivers/i2c/i2c_driver.c"]));
+        let labels = vec!["Size: M".to_string()];
+
+        // Each Commit changes a different Subsystem: From the Commit Files, or the Commit Title
+        assert_eq!(check_squash(&rules, &commits, &commit_files, &labels), "");
+
+        // Third Commit changes the same Subsystem as the First Commit
+        commit_files.insert("3333333cccc".to_string(), files(&["arch/risc-v/src/esp32c3/esp32c3_irq.c"]));
+        assert_eq!(check_squash(&rules, &commits, &commit_files, &labels), concat!(
+            "__Squash The Commits:__ This PR contains 3 Commits. Please Squash the Multiple Commits into a Single Commit, or One Commit per Subsystem. ",
+            "To keep the Commits, please ask a Maintainer to add the `multi-commit` label:\n",
+            "* `1111111`: arch/risc-v: Fix the build (`arch/risc-v`)\n",
+            "* `2222222`: drivers/i2c: Fix the driver (`drivers/i2c`)\n",
+            "* `3333333`: esp32c3: Fix the IRQ (`arch/risc-v`), same Subsystem as `1111111`\n\n",
+        ));
+
+        // Multi-Commit Label allows Multiple Commits
+        let labels = vec!["Size: M".to_string(), "multi-commit".to_string()];
+        assert_eq!(check_squash(&rules, &commits, &commit_files, &labels), "");
+
+        // Up to Max Commits are allowed. Squash isn't checked by default.
+        let rules = CommitRules { max_commits: 3, ..strict_rules() };
+        assert_eq!(check_squash(&rules, &commits, &commit_files, &[]), "");
+        assert_eq!(check_squash(&CommitRules::default(), &commits, &commit_files, &[]), "");
+
+        // Titles without Subsystem: The Commit Body is not the Subsystem
+        let untitled = [
+            commit("4444444dddd", "Fix the build\n\nSigned-off-by: A <a@b>"),
+            commit("5555555eeee", "Fix the driver\n\nSigned-off-by: A <a@b>"),
+        ];
+        assert_eq!(check_squash(&strict_rules(), &untitled, &BTreeMap::new(), &[]), concat!(
+            "__Squash The Commits:__ This PR contains 2 Commits. Please Squash the Multiple Commits into a Single Commit, or One Commit per Subsystem. ",
+            "To keep the Commits, please ask a Maintainer to add the `multi-commit` label:\n",
+            "* `4444444`: Fix the build\n",
+            "* `5555555`: Fix the driver\n\n",
+        ));
+
+        // Without Subsystems: Every Multiple Commit is reported
+        let rules = CommitRules { allow_subsystems: false, multi_commit_labels: vec![], ..strict_rules() };
+        let precheck = check_squash(&rules, &commits[..2], &commit_files, &[]);
+        assert!(precheck.starts_with("__Squash The Commits:__ This PR contains 2 Commits. Please Squash the Multiple Commits into a Single Commit:\n"));
+    }
+
+    #[test]
+    fn check_commit_messages() {
+        let commits = [
+            commit("1111111aaaa", "arch/risc-v: Fix the build\n\nBody text.\n\nSigned-off-by: A <a@b>"),
+            commit("2222222bbbb", "Fix the driver"),
+        ];
+        let precheck = check_commits(&strict_rules(), &commits, &BTreeMap::new());
+        assert!(precheck.starts_with("__Fix The Commit Messages:__ Please fix the following Commits, then force-push the PR:\n* `2222222`: Commit Message is empty."));
+        assert!(!precheck.contains("`1111111`"));
+        assert_eq!(check_commits(&strict_rules(), &commits[..1], &BTreeMap::new()), "");
+    }
+}
@@ -0,0 +1,582 @@
+//! TOML Configuration File for the Repos, Rules and Prompts.
+//!   Global Settings apply to all Repos.
+//!   Each Repo may override the Bot Login, Header, Requirements, Prompt Templates, Skip Labels, Skip Unlabeled, PR Labels and Commit Rules.
+//!   Settings from the Command Line take priority over the Configuration File.
+//! See `nuttx-pr-bot.toml` for an example.
+
+use std::{fs, path::Path};
+use serde::Deserialize;
+use crate::{
+    commits::CommitRules,
+    labels::LabelConfig,
+    forge::ForgeKind,
+    model::{ModelArgs, ModelKind},
+    prompt::{self, SYSTEM_TEMPLATE, USER_TEMPLATE},
+    template::{find_section, parse_sections, SECTIONS},
+};
+
+/// Default Login of the Bot, which creates the PR Reactions
+const BOT_LOGIN: &str = "nuttxpr";
+
+/// Default Number of Newest PRs to be processed
+const PR_COUNT: u8 = 20;
+
+/// Default Seconds to wait after processing each PR
+const PR_INTERVAL: u64 = 5;
+
+/// Default Seconds to wait after posting a PR Comment
+const COMMENT_INTERVAL: u64 = 60;
+
+/// Default Seconds to wait after processing each Repo, in Daemon Mode
+const REPO_INTERVAL: u64 = 300;
+
+/// Default Labels that will skip the PR
+const SKIP_LABELS: [&str; 1] = ["Size: XS"];
+
+/// Default Name of the Check Run
+const CHECK_NAME: &str = "PR Template";
+
+/// Default Context of the Commit Status
+const STATUS_CONTEXT: &str = "nuttx-pr-bot/template";
+
+/// Configuration File
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Login of the Bot, which creates the PR Reactions (`nuttxpr`)
+    pub bot_login: Option<String>,
+
+    /// Header for PR Comment (Markdown)
+    pub header: Option<String>,
+
+    /// Requirements for PR Review (Markdown)
+    pub requirements: Option<String>,
+
+    /// File containing the Requirements for PR Review, relative to the Configuration File
+    pub requirements_file: Option<String>,
+
+    /// Labels that will skip the PR, like `Size: XS`
+    pub skip_labels: Option<Vec<String>>,
+
+    /// Skip the PR without Labels, because the PR Size is Unknown (default: true for GitHub, false for other Forges)
+    pub skip_unlabeled: Option<bool>,
+
+    /// File containing the System Prompt Template, relative to the Configuration File
+    pub system_prompt_file: Option<String>,
+
+    /// File containing the User Prompt Template, relative to the Configuration File
+    pub user_prompt_file: Option<String>,
+
+    /// System Prompt Template, loaded from `system_prompt_file`
+    #[serde(skip)]
+    pub system_prompt: Option<String>,
+
+    /// User Prompt Template, loaded from `user_prompt_file`
+    #[serde(skip)]
+    pub user_prompt: Option<String>,
+
+    /// PR Labels for the LLM Verdict (`[labels]`)
+    pub labels: Option<LabelConfig>,
+
+    /// Rules for the Commit Messages (`[commits]`)
+    pub commits: Option<CommitRules>,
+
+    /// Publish the PR Review as a Check Run on the PR Head Commit (GitHub Apps only)
+    pub check_run: Option<bool>,
+
+    /// Name of the Check Run (`PR Template`)
+    pub check_name: Option<String>,
+
+    /// Post the Commit Status on the PR Head Commit, updated on every review
+    pub commit_status: Option<bool>,
+
+    /// Context of the Commit Status (`nuttx-pr-bot/template`)
+    pub status_context: Option<String>,
+
+    /// LLM Backend that will review the PRs (`gemini`, `ollama`, `llama-cpp`, `openai`, `mock`)
+    pub model: Option<ModelKind>,
+
+    /// Base URL of the LLM Server
+    pub llm_url: Option<String>,
+
+    /// Name of the Model served by the LLM Server
+    pub llm_model: Option<String>,
+
+    /// Max Number of attempts to review a PR with the LLM (State Store only, PR Reactions allow 3)
+    pub max_attempts: Option<u32>,
+
+    /// Number of Newest PRs to be processed (1 to 100)
+    pub pr_count: Option<u8>,
+
+    /// Seconds to wait after processing each PR
+    pub pr_interval: Option<u64>,
+
+    /// Seconds to wait after posting a PR Comment
+    pub comment_interval: Option<u64>,
+
+    /// Seconds to wait after processing each Repo, in Daemon Mode
+    pub repo_interval: Option<u64>,
+
+    /// Repos that will be processed
+    pub repos: Vec<RepoConfig>,
+}
+
+/// Configuration for a Repo, overriding the Global Settings
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RepoConfig {
+    /// Owner of the Repo (`apache`)
+    pub owner: String,
+
+    /// Name of the Repo (`nuttx` or `nuttx-apps`)
+    pub repo: String,
+
+    /// Login of the Bot
+    pub bot_login: Option<String>,
+
+    /// Header for PR Comment
+    pub header: Option<String>,
+
+    /// Requirements for PR Review
+    pub requirements: Option<String>,
+
+    /// File containing the Requirements for PR Review, relative to the Configuration File
+    pub requirements_file: Option<String>,
+
+    /// Labels that will skip the PR
+    pub skip_labels: Option<Vec<String>>,
+
+    /// Skip the PR without Labels
+    pub skip_unlabeled: Option<bool>,
+
+    /// File containing the System Prompt Template, relative to the Configuration File
+    pub system_prompt_file: Option<String>,
+
+    /// File containing the User Prompt Template, relative to the Configuration File
+    pub user_prompt_file: Option<String>,
+
+    /// System Prompt Template, loaded from `system_prompt_file`
+    #[serde(skip)]
+    pub system_prompt: Option<String>,
+
+    /// User Prompt Template, loaded from `user_prompt_file`
+    #[serde(skip)]
+    pub user_prompt: Option<String>,
+
+    /// PR Labels for the LLM Verdict (`[repos.labels]`), replacing the Global PR Labels
+    pub labels: Option<LabelConfig>,
+
+    /// Rules for the Commit Messages (`[repos.commits]`), replacing the Global Commit Rules
+    pub commits: Option<CommitRules>,
+}
+
+/// Settings for processing the PRs in a Repo
+#[derive(Debug, Clone)]
+pub struct Settings {
+    /// Login of the Bot, which creates the PR Reactions
+    pub bot_login: String,
+
+    /// Header for PR Comment
+    pub header: String,
+
+    /// Requirements for PR Review
+    pub requirements: String,
+
+    /// Labels that will skip the PR
+    pub skip_labels: Vec<String>,
+
+    /// Skip the PR without Labels, because the PR Size is Unknown
+    pub skip_unlabeled: bool,
+
+    /// System Prompt Template (minijinja)
+    pub system_prompt: String,
+
+    /// User Prompt Template (minijinja)
+    pub user_prompt: String,
+
+    /// PR Labels for the LLM Verdict
+    pub labels: LabelConfig,
+
+    /// Rules for the Commit Messages
+    pub commits: CommitRules,
+
+    /// Publish the PR Review as a Check Run on the PR Head Commit
+    pub check_run: bool,
+
+    /// Name of the Check Run
+    pub check_name: String,
+
+    /// Post the Commit Status on the PR Head Commit
+    pub commit_status: bool,
+
+    /// Context of the Commit Status
+    pub status_context: String,
+
+    /// Max Number of attempts to review a PR with the LLM
+    pub max_attempts: u32,
+
+    /// Number of Newest PRs to be processed
+    pub pr_count: u8,
+
+    /// Seconds to wait after processing each PR
+    pub pr_interval: u64,
+
+    /// Seconds to wait after posting a PR Comment
+    pub comment_interval: u64,
+}
+
+impl Default for Settings {
+    /// Default Settings, same as the NuttX Bot
+    fn default() -> Self {
+        Self {
+            bot_login: BOT_LOGIN.to_string(),
+            header: crate::HEADER.to_string(),
+            requirements: crate::REQUIREMENTS.to_string(),
+            skip_labels: SKIP_LABELS.iter().map(|l| l.to_string()).collect(),
+            skip_unlabeled: true,
+            system_prompt: SYSTEM_TEMPLATE.to_string(),
+            user_prompt: USER_TEMPLATE.to_string(),
+            labels: LabelConfig::default(),
+            commits: CommitRules::default(),
+            check_run: false,
+            check_name: CHECK_NAME.to_string(),
+            commit_status: false,
+            status_context: STATUS_CONTEXT.to_string(),
+            max_attempts: crate::MAX_ATTEMPTS,
+            pr_count: PR_COUNT,
+            pr_interval: PR_INTERVAL,
+            comment_interval: COMMENT_INTERVAL,
+        }
+    }
+}
+
+impl Config {
+    /// Load and validate the Configuration File
+    pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let toml = fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read Config {}: {}", path, e))?;
+        let mut config: Config = toml::from_str(&toml)
+            .map_err(|e| format!("Invalid Config {}: {}", path, e))?;
+
+        // Load the Requirements Files and Prompt Templates, relative to the Configuration File
+        let dir = Path::new(path).parent().unwrap_or(Path::new("."));
+        config.requirements = load_requirements(dir, config.requirements.take(), &config.requirements_file)
+            .map_err(|e| format!("Invalid Config {}: {}", path, e))?;
+        config.system_prompt = load_file(dir, &config.system_prompt_file)
+            .map_err(|e| format!("Invalid Config {}: {}", path, e))?;
+        config.user_prompt = load_file(dir, &config.user_prompt_file)
+            .map_err(|e| format!("Invalid Config {}: {}", path, e))?;
+        for repo in config.repos.iter_mut() {
+            let name = format!("Repo {}/{}", repo.owner, repo.repo);
+            repo.requirements = load_requirements(dir, repo.requirements.take(), &repo.requirements_file)
+                .map_err(|e| format!("Invalid Config {}: {}: {}", path, name, e))?;
+            repo.system_prompt = load_file(dir, &repo.system_prompt_file)
+                .map_err(|e| format!("Invalid Config {}: {}: {}", path, name, e))?;
+            repo.user_prompt = load_file(dir, &repo.user_prompt_file)
+                .map_err(|e| format!("Invalid Config {}: {}: {}", path, name, e))?;
+        }
+
+        // Validate the Configuration
+        config.validate()
+            .map_err(|e| format!("Invalid Config {}: {}", path, e))?;
+        Ok(config)
+    }
+
+    /// Return the Repos (Owner, Name) in the Configuration File
+    pub fn repos(&self) -> Vec<(String, String)> {
+        self.repos
+            .iter()
+            .map(|r| (r.owner.clone(), r.repo.clone()))
+            .collect()
+    }
+
+    /// Return the Settings for the Repo: Repo Config > Global Config > Defaults.
+    /// PRs without Labels are skipped by default only if the Forge applies the PR Size Labels.
+    pub fn settings(&self, owner: &str, repo: &str, forge: ForgeKind) -> Settings {
+        let default = Settings::default();
+        let repo_config = self.repos
+            .iter()
+            .find(|r| r.owner == owner && r.repo == repo);
+        let pick = |repo_value: Option<&String>, global_value: &Option<String>, default_value: String|
+            repo_value.or(global_value.as_ref()).cloned().unwrap_or(default_value);
+        Settings {
+            bot_login: pick(repo_config.and_then(|r| r.bot_login.as_ref()), &self.bot_login, default.bot_login),
+            header: pick(repo_config.and_then(|r| r.header.as_ref()), &self.header, default.header),
+            requirements: pick(repo_config.and_then(|r| r.requirements.as_ref()), &self.requirements, default.requirements),
+            skip_labels: repo_config.and_then(|r| r.skip_labels.clone())
+                .or(self.skip_labels.clone())
+                .unwrap_or(default.skip_labels),
+            skip_unlabeled: repo_config.and_then(|r| r.skip_unlabeled)
+                .or(self.skip_unlabeled)
+                .unwrap_or(default.skip_unlabeled && forge.has_size_labels()),
+            system_prompt: pick(repo_config.and_then(|r| r.system_prompt.as_ref()), &self.system_prompt, default.system_prompt),
+            user_prompt: pick(repo_config.and_then(|r| r.user_prompt.as_ref()), &self.user_prompt, default.user_prompt),
+            labels: repo_config.and_then(|r| r.labels.clone())
+                .or(self.labels.clone())
+                .unwrap_or(default.labels),
+            commits: repo_config.and_then(|r| r.commits.clone())
+                .or(self.commits.clone())
+                .unwrap_or(default.commits),
+            check_run: self.check_run.unwrap_or(default.check_run),
+            check_name: self.check_name.clone().unwrap_or(default.check_name),
+            commit_status: self.commit_status.unwrap_or(default.commit_status),
+            status_context: self.status_context.clone().unwrap_or(default.status_context),
+            max_attempts: self.max_attempts.unwrap_or(default.max_attempts),
+            pr_count: self.pr_count.unwrap_or(default.pr_count),
+            pr_interval: self.pr_interval.unwrap_or(default.pr_interval),
+            comment_interval: self.comment_interval.unwrap_or(default.comment_interval),
+        }
+    }
+
+    /// Return the Seconds to wait after processing each Repo, in Daemon Mode
+    pub fn repo_interval(&self) -> u64 {
+        self.repo_interval.unwrap_or(REPO_INTERVAL)
+    }
+
+    /// Return the LLM Backend Args: Command Line > Config
+    pub fn model_args(&self, args: &ModelArgs) -> ModelArgs {
+        ModelArgs {
+            model: args.model.or(self.model),
+            llm_url: args.llm_url.clone().or(self.llm_url.clone()),
+            llm_model: args.llm_model.clone().or(self.llm_model.clone()),
+            mock_response: args.mock_response.clone(),
+        }
+    }
+
+    /// Validate the Configuration
+    fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        // Validate the Global Settings
+        if let Some(pr_count) = self.pr_count {
+            if !(1..=100).contains(&pr_count) {
+                return Err(format!("`pr_count` should be 1 to 100, not {}", pr_count).into());
+            }
+        }
+        if self.max_attempts == Some(0) {
+            return Err("`max_attempts` should be at least 1".into());
+        }
+        if self.check_name.as_ref().is_some_and(|n| n.trim().is_empty()) {
+            return Err("`check_name` should not be empty".into());
+        }
+        if self.status_context.as_ref().is_some_and(|c| c.trim().is_empty()) {
+            return Err("`status_context` should not be empty".into());
+        }
+        if self.repo_interval == Some(0) {
+            return Err("`repo_interval` should be at least 1 second".into());
+        }
+
+        // Validate the Repos
+        let mut names: Vec<String> = vec![];
+        for repo in self.repos.iter() {
+            let name = format!("{}/{}", repo.owner, repo.repo);
+            if repo.owner.is_empty() || repo.repo.is_empty() {
+                return Err(format!("Repo `{}` should have `owner` and `repo`", name).into());
+            }
+            if names.contains(&name) {
+                return Err(format!("Repo `{}` is duplicated", name).into());
+            }
+            names.push(name);
+        }
+
+        // Validate the Settings for each Repo, and the Global Settings
+        let mut settings: Vec<(String, Settings)> = self.repos
+            .iter()
+            .map(|r| (format!("Repo {}/{}", r.owner, r.repo), self.settings(&r.owner, &r.repo, ForgeKind::Github)))
+            .collect();
+        settings.push(("Global Settings".to_string(), self.settings("", "", ForgeKind::Github)));
+        for (name, settings) in settings {
+            if settings.bot_login.trim().is_empty() {
+                return Err(format!("{}: `bot_login` should not be empty", name).into());
+            }
+            if settings.header.trim().is_empty() {
+                return Err(format!("{}: `header` should not be empty", name).into());
+            }
+
+            // Requirements should contain the Summary, Impact and Testing sections
+            let sections = parse_sections(&settings.requirements);
+            for section in SECTIONS {
+                if find_section(&sections, section).is_none() {
+                    return Err(format!("{}: Requirements should contain the `## {}` section", name, section).into());
+                }
+            }
+
+            // Squash the Commits only if there are more than Max Commits
+            if settings.commits.max_commits == 0 {
+                return Err(format!("{}: `max_commits` should be at least 1", name).into());
+            }
+
+            // PR Labels should be distinct
+            let labels = settings.labels.managed();
+            if let Some(label) = labels.iter().enumerate().find_map(|(i, l)| labels[..i].contains(l).then_some(l)) {
+                return Err(format!("{}: Label `{}` is used more than once", name, label).into());
+            }
+
+            // Prompt Templates should render without errors
+            prompt::check_template(&settings.system_prompt)
+                .map_err(|e| format!("{}: Invalid System Prompt Template: {}", name, e))?;
+            prompt::check_template(&settings.user_prompt)
+                .map_err(|e| format!("{}: Invalid User Prompt Template: {}", name, e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Return the Requirements, or load them from the Requirements File (relative to the Config Directory)
+fn load_requirements(dir: &Path, requirements: Option<String>, requirements_file: &Option<String>) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    match (requirements, requirements_file) {
+        (Some(_), Some(_)) =>
+            Err("Specify either `requirements` or `requirements_file`, not both".into()),
+        (None, file) => load_file(dir, file),
+        (requirements, None) => Ok(requirements),
+    }
+}
+
+/// Load the File (relative to the Config Directory), if specified
+fn load_file(dir: &Path, file: &Option<String>) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    match file {
+        Some(file) => {
+            let path = dir.join(file);
+            let text = fs::read_to_string(&path)
+                .map_err(|e| format!("Unable to read File {}: {}", path.display(), e))?;
+            Ok(Some(text))
+        }
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write the Config File (and the other Files) to a new Temp Directory, then load the Config File
+    fn load(name: &str, toml: &str, files: &[(&str, &str)]) -> Result<Config, Box<dyn std::error::Error>> {
+        let dir = std::env::temp_dir()
+            .join(format!("nuttx-pr-bot-config-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for (file, text) in files {
+            fs::write(dir.join(file), text).unwrap();
+        }
+        let path = dir.join("nuttx-pr-bot.toml");
+        fs::write(&path, toml).unwrap();
+        let config = Config::load(path.to_str().unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+        config
+    }
+
+    /// Load the Config File. Return the error message.
+    fn load_error(name: &str, toml: &str, files: &[(&str, &str)]) -> String {
+        load(name, toml, files).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn sample_config() {
+        let toml = include_str!("../nuttx-pr-bot.toml");
+        let config = load("sample", toml, &[]).unwrap();
+        assert_eq!(config.repos(), vec![
+            ("apache".to_string(), "nuttx".to_string()),
+            ("apache".to_string(), "nuttx-apps".to_string()),
+        ]);
+        assert!(!config.settings("apache", "nuttx", ForgeKind::Github).labels.enabled);
+    }
+
+    #[test]
+    fn invalid_config() {
+        let repo = "[[repos]]\nowner = \"apache\"\nrepo = \"nuttx\"\n";
+        let cases = [
+            (format!("{repo}{repo}"), "Repo `apache/nuttx` is duplicated"),
+            ("pr_count = 0".to_string(), "`pr_count` should be 1 to 100, not 0"),
+            ("pr_count = 101".to_string(), "`pr_count` should be 1 to 100, not 101"),
+            ("max_attempts = 0".to_string(), "`max_attempts` should be at least 1"),
+            ("[[repos]]\nowner = \"apache\"\n".to_string(), "Repo `apache/` should have `owner` and `repo`"),
+            ("bot_login = \" \"".to_string(), "Global Settings: `bot_login` should not be empty"),
+            (format!("{repo}header = \"\""), "Repo apache/nuttx: `header` should not be empty"),
+            ("requirements = \"## Summary\\n## Impact\\n\"".to_string(), "Requirements should contain the `## Testing` section"),
+            ("[commits]\nmax_commits = 0".to_string(), "`max_commits` should be at least 1"),
+            ("[labels]\nok = \"needs: summary\"".to_string(), "Label `needs: summary` is used more than once"),
+            ("unknown = 1".to_string(), "unknown field `unknown`"),
+        ];
+        for (i, (toml, expected)) in cases.iter().enumerate() {
+            let err = load_error(&format!("invalid-{i}"), toml, &[]);
+            assert!(err.starts_with("Invalid Config "), "{:?}: {:?}", toml, err);
+            assert!(err.contains(expected), "{:?}: {:?}", toml, err);
+        }
+    }
+
+    #[test]
+    fn invalid_files() {
+        // Requirements and Requirements File can't be both set
+        let requirements = "## Summary\n## Impact\n## Testing\n";
+        let toml = "requirements = \"## Summary\"\nrequirements_file = \"requirements.md\"";
+        let err = load_error("both", toml, &[("requirements.md", requirements)]);
+        assert!(err.contains("Specify either `requirements` or `requirements_file`, not both"), "{}", err);
+        let toml = "[[repos]]\nowner = \"apache\"\nrepo = \"nuttx\"\nrequirements = \"## Summary\"\nrequirements_file = \"requirements.md\"";
+        let err = load_error("repo-both", toml, &[("requirements.md", requirements)]);
+        assert!(err.contains("Repo apache/nuttx: Specify either"), "{}", err);
+
+        // Missing File
+        let err = load_error("missing", "requirements_file = \"missing.md\"", &[]);
+        assert!(err.contains("Unable to read File"), "{}", err);
+
+        // Unknown Variable in the Prompt Template
+        let toml = "user_prompt_file = \"user.md\"";
+        let err = load_error("variable", toml, &[("user.md", "{{ body }} {{ unknown_var }}")]);
+        assert!(err.contains("Global Settings: Invalid User Prompt Template"), "{}", err);
+
+        // Syntax Error in the Prompt Template
+        let toml = "[[repos]]\nowner = \"apache\"\nrepo = \"nuttx\"\nsystem_prompt_file = \"system.md\"";
+        let err = load_error("syntax", toml, &[("system.md", "{% if requirements %}")]);
+        assert!(err.contains("Repo apache/nuttx: Invalid System Prompt Template"), "{}", err);
+    }
+
+    #[test]
+    fn settings_override() {
+        let toml = r#"
+header = "Global Header"
+pr_count = 10
+
+[commits]
+title_format = true
+squash = true
+
+[[repos]]
+owner = "apache"
+repo = "nuttx"
+header = "NuttX Header"
+requirements_file = "requirements.md"
+skip_unlabeled = false
+
+[repos.commits]
+signed_off_by = true
+
+[[repos]]
+owner = "apache"
+repo = "nuttx-apps"
+"#;
+        let requirements = "## Summary\nNuttX\n## Impact\n## Testing\n";
+        let config = load("override", toml, &[("requirements.md", requirements)]).unwrap();
+
+        // Repo Config > Global Config > Defaults
+        let nuttx = config.settings("apache", "nuttx", ForgeKind::Github);
+        let apps = config.settings("apache", "nuttx-apps", ForgeKind::Github);
+        assert_eq!(nuttx.header, "NuttX Header");
+        assert_eq!(apps.header, "Global Header");
+        assert_eq!(nuttx.requirements, requirements);
+        assert_eq!(apps.requirements, crate::REQUIREMENTS);
+        assert_eq!(nuttx.pr_count, 10);
+        assert_eq!(apps.bot_login, BOT_LOGIN);
+
+        // Repo Commit Rules replace the Global Commit Rules
+        assert!(nuttx.commits.signed_off_by);
+        assert!(!nuttx.commits.title_format);
+        assert!(!nuttx.commits.squash);
+        assert!(nuttx.commits.require_body);
+        assert!(!apps.commits.signed_off_by);
+        assert!(apps.commits.title_format);
+        assert!(apps.commits.squash);
+
+        // Skip the PRs without Labels on GitHub only, unless configured
+        assert!(!nuttx.skip_unlabeled);
+        assert!(apps.skip_unlabeled);
+        assert!(!config.settings("apache", "nuttx-apps", ForgeKind::Gitea).skip_unlabeled);
+        assert!(!config.settings("apache", "nuttx-apps", ForgeKind::Gitlab).skip_unlabeled);
+    }
+}
@@ -0,0 +1,83 @@
+//! Daemon Mode: Process the PRs for a list of Repos forever, inside one process.
+//!   For every Repo: Process the Newest PRs, then wait for the interval.
+//!   Quit gracefully on SIGTERM or Ctrl-C, after the current PR is done.
+
+use std::{sync::{atomic::{AtomicBool, Ordering}, Arc}, time::Duration};
+use log::{error, info};
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    sync::Notify,
+};
+use crate::{config::Settings, forge::Forge, model::ReviewModel, state::StateStore};
+
+/// Set when SIGTERM or Ctrl-C is received. The Daemon will quit after the current PR.
+static SHUTDOWN: AtomicBool = AtomicBool::new(false);
+
+/// Repo processed by the Daemon
+pub struct Repo {
+    /// Forge that hosts the Repo
+    pub forge: Box<dyn Forge>,
+
+    /// State Store for the PRs in the Repo, if specified
+    pub state: Option<StateStore>,
+
+    /// Settings for the Repo, from the Config File
+    pub settings: Settings,
+}
+
+/// Parse the Repos like `apache/nuttx,apache/nuttx-apps` into (Owner, Name)
+pub fn parse_repos(repos: &[String]) -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
+    let mut result = vec![];
+    for repo in repos {
+        let (owner, name) = repo
+            .split_once('/')
+            .ok_or(format!("Repo should be `owner/repo`: {}", repo))?;
+        result.push((owner.to_string(), name.to_string()));
+    }
+    Ok(result)
+}
+
+/// Return true if SIGTERM or Ctrl-C was received, so we should stop before the next PR
+pub fn is_shutdown() -> bool {
+    SHUTDOWN.load(Ordering::SeqCst)
+}
+
+/// Process the PRs for every Repo, waiting for the interval after each Repo.
+/// Errors are logged and the Daemon continues with the next Repo.
+/// On SIGTERM or Ctrl-C: Finish the current PR, then quit. The PR is never interrupted,
+/// so the PR Comment, PR Reactions and State Store stay consistent.
+pub async fn run_daemon(repos: &[Repo], model: Option<&dyn ReviewModel>, interval: Duration) -> Result<(), Box<dyn std::error::Error>> {
+    // Watch for SIGTERM or Ctrl-C in the background
+    let mut sigterm = signal(SignalKind::terminate())?;
+    let notify = Arc::new(Notify::new());
+    let notify_signal = notify.clone();
+    tokio::spawn(async move {
+        tokio::select! {
+            _ = sigterm.recv() => info!("Received SIGTERM, quitting after the current PR"),
+            _ = tokio::signal::ctrl_c() => info!("Received Ctrl-C, quitting after the current PR"),
+        }
+        SHUTDOWN.store(true, Ordering::SeqCst);
+        notify_signal.notify_one();
+    });
+
+    loop {
+        for repo in repos {
+            // Process the PRs for the Repo. Quit if SIGTERM or Ctrl-C.
+            info!("Processing Repo: {}", repo.forge.name());
+            if let Err(err) = crate::process_repo(repo, model).await {
+                error!("Failed to process Repo {}: {}", repo.forge.name(), err);
+            }
+            if is_shutdown() {
+                info!("Quitting");
+                return Ok(());
+            }
+
+            // Wait for the interval. Quit if SIGTERM or Ctrl-C.
+            info!("Waiting {} seconds", interval.as_secs());
+            tokio::select! {
+                _ = tokio::time::sleep(interval) => {}
+                _ = notify.notified() => { info!("Quitting"); return Ok(()); }
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! Dry-Run Forge: Wraps another Forge and touches nothing on the Forge.
+//!   PRs, Commits, Changed Files and Reactions are fetched from the wrapped Forge.
+//!   PR Comments are written to stdout, or to a directory of Markdown Files.
+//!   PR Reactions, PR Labels, Check Runs and Commit Statuses are not created or deleted.
+
+use std::{fs, path::Path};
+use async_trait::async_trait;
+use log::info;
+use super::{ChangedFile, CheckRun, Comment, Commit, CommitStatus, Forge, PullRequest, Reaction, ReactionContent};
+
+/// Dry-Run Forge
+pub struct DryRun {
+    /// Wrapped Forge that will be read
+    inner: Box<dyn Forge>,
+
+    /// Directory for the PR Comments. If None: Write to stdout.
+    output_dir: Option<String>,
+
+    /// Prefix for the Markdown Files, like `apache-nuttx`
+    prefix: String,
+}
+
+impl DryRun {
+    /// Wrap the Forge for the Repo (Owner, Name). Write the PR Comments to the directory, or stdout if None.
+    pub fn new(inner: Box<dyn Forge>, output_dir: Option<&str>, owner: &str, repo: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        if let Some(dir) = output_dir {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Unable to create Output Dir {}: {}", dir, e))?;
+        }
+        Ok(Self {
+            inner,
+            output_dir: output_dir.map(|d| d.to_string()),
+            prefix: format!("{}-{}", owner, repo),
+        })
+    }
+}
+
+#[async_trait(?Send)]
+impl Forge for DryRun {
+    /// Name of the Wrapped Forge
+    fn name(&self) -> String {
+        format!("dry-run {}", self.inner.name())
+    }
+
+    /// Dry Run touches nothing on the Forge
+    fn is_dry_run(&self) -> bool {
+        true
+    }
+
+    /// Fetch the Newest Pull Requests from the Wrapped Forge
+    async fn list_pulls(&self, count: u8) -> Result<Vec<PullRequest>, Box<dyn std::error::Error>> {
+        self.inner.list_pulls(count).await
+    }
+
+    /// Fetch the Pull Request from the Wrapped Forge
+    async fn get_pull(&self, pr_id: u64) -> Result<PullRequest, Box<dyn std::error::Error>> {
+        self.inner.get_pull(pr_id).await
+    }
+
+    /// Fetch the Commits from the Wrapped Forge
+    async fn list_commits(&self, pr_id: u64) -> Result<Vec<Commit>, Box<dyn std::error::Error>> {
+        self.inner.list_commits(pr_id).await
+    }
+
+    /// Fetch the Changed Files from the Wrapped Forge
+    async fn list_files(&self, pr_id: u64) -> Result<Vec<ChangedFile>, Box<dyn std::error::Error>> {
+        self.inner.list_files(pr_id).await
+    }
+
+    /// Fetch the Commit Files from the Wrapped Forge
+    async fn list_commit_files(&self, sha: &str) -> Result<Vec<ChangedFile>, Box<dyn std::error::Error>> {
+        self.inner.list_commit_files(sha).await
+    }
+
+    /// Fetch the Reactions from the Wrapped Forge
+    async fn list_reactions(&self, pr_id: u64) -> Result<Vec<Reaction>, Box<dyn std::error::Error>> {
+        self.inner.list_reactions(pr_id).await
+    }
+
+    /// Don't create the Reaction
+    async fn create_reaction(&self, pr_id: u64, content: ReactionContent) -> Result<(), Box<dyn std::error::Error>> {
+        info!("Dry Run: Skip Create Reaction {:?} on PR {}", content, pr_id);
+        Ok(())
+    }
+
+    /// Don't delete the Reaction
+    async fn delete_reaction(&self, pr_id: u64, reaction_id: u64) -> Result<(), Box<dyn std::error::Error>> {
+        info!("Dry Run: Skip Delete Reaction {} on PR {}", reaction_id, pr_id);
+        Ok(())
+    }
+
+    /// Write the PR Comment to stdout, or to the Markdown File `<prefix>-<pr_id>.md`
+    async fn create_comment(&self, pr_id: u64, body: &str) -> Result<Comment, Box<dyn std::error::Error>> {
+        let url = match &self.output_dir {
+            Some(dir) => {
+                let path = Path::new(dir).join(format!("{}-{}.md", self.prefix, pr_id));
+                fs::write(&path, body)?;
+                info!("Dry Run: Wrote PR Comment to {}", path.display());
+                path.display().to_string()
+            }
+            None => {
+                println!("<!-- Dry Run: PR Comment for {} #{} -->\n{}\n", self.prefix, pr_id, body);
+                "stdout".to_string()
+            }
+        };
+        Ok(Comment { id: 0, url })
+    }
+
+    /// Don't add the Labels
+    async fn add_labels(&self, pr_id: u64, labels: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+        info!("Dry Run: Skip Add Labels {:?} on PR {}", labels, pr_id);
+        Ok(())
+    }
+
+    /// Don't remove the Label
+    async fn remove_label(&self, pr_id: u64, label: &str) -> Result<(), Box<dyn std::error::Error>> {
+        info!("Dry Run: Skip Remove Label {:?} on PR {}", label, pr_id);
+        Ok(())
+    }
+
+    /// Don't create the Check Run
+    async fn create_check_run(&self, check: &CheckRun) -> Result<(), Box<dyn std::error::Error>> {
+        info!("Dry Run: Skip Check Run {:?} on {}: {:?}", check.name, check.head_sha, check.conclusion);
+        Ok(())
+    }
+
+    /// Don't create the Commit Status
+    async fn create_status(&self, status: &CommitStatus) -> Result<(), Box<dyn std::error::Error>> {
+        info!("Dry Run: Skip Commit Status {:?} on {}: {:?}", status.context, status.sha, status.state);
+        Ok(())
+    }
+}
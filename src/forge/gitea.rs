@@ -0,0 +1,398 @@
+//! Gitea / Forgejo / Codeberg as Code Forge, via the Gitea REST API.
+//! See https://gitea.com/api/swagger
+
+use std::env;
+use async_trait::async_trait;
+use log::info;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use super::{ChangedFile, CheckRun, Comment, Commit, CommitStatus, Forge, PullRequest, Reaction, ReactionContent, StatusState};
+
+/// Default Base URL of the Gitea Server
+pub const DEFAULT_URL: &str = "https://codeberg.org";
+
+/// Items per Page for the Gitea API. Gitea caps the Page Size at `MAX_RESPONSE_ITEMS` (50 by default).
+const PAGE_LIMIT: usize = 50;
+
+/// Max Pages fetched for Commits, Files and Labels
+const MAX_PAGES: usize = 100;
+
+/// Gitea Repo
+pub struct Gitea {
+    /// HTTP Client
+    client: reqwest::Client,
+
+    /// Base URL of the Gitea API, like `https://codeberg.org/api/v1`
+    api_url: String,
+
+    /// Access Token from `GITEA_TOKEN`
+    token: String,
+
+    /// Owner of the Gitea Repo
+    owner: String,
+
+    /// Name of the Gitea Repo
+    repo: String,
+}
+
+/// Gitea User
+#[derive(Deserialize)]
+struct GiteaUser {
+    login: String,
+}
+
+/// Gitea Label
+#[derive(Deserialize)]
+struct GiteaLabel {
+    #[serde(default)]
+    id: u64,
+    name: String,
+}
+
+/// Request to Add the Gitea Labels (by ID)
+#[derive(Serialize)]
+struct GiteaLabelsRequest {
+    labels: Vec<u64>,
+}
+
+/// Gitea Branch Info for the PR Head
+#[derive(Deserialize)]
+struct GiteaBranch {
+    sha: String,
+}
+
+/// Gitea Pull Request
+#[derive(Deserialize)]
+struct GiteaPull {
+    number: u64,
+    html_url: String,
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    user: Option<GiteaUser>,
+    state: String,
+    #[serde(default)]
+    comments: u64,
+    #[serde(default)]
+    labels: Vec<GiteaLabel>,
+    head: GiteaBranch,
+}
+
+/// Gitea Commit
+#[derive(Deserialize)]
+struct GiteaCommit {
+    sha: String,
+    commit: GiteaCommitDetail,
+}
+
+/// Gitea Commit Details
+#[derive(Deserialize)]
+struct GiteaCommitDetail {
+    message: String,
+}
+
+/// Gitea Commit with the Changed Files
+#[derive(Deserialize)]
+struct GiteaCommitFiles {
+    #[serde(default)]
+    files: Option<Vec<GiteaChangedFile>>,
+}
+
+/// Gitea Changed File
+#[derive(Deserialize)]
+struct GiteaChangedFile {
+    filename: String,
+    #[serde(default)]
+    additions: u64,
+    #[serde(default)]
+    deletions: u64,
+}
+
+/// Gitea Reaction. Gitea Reactions don't have an ID.
+#[derive(Deserialize)]
+struct GiteaReaction {
+    user: GiteaUser,
+    content: String,
+}
+
+/// Request to Create or Delete a Gitea Reaction
+#[derive(Serialize)]
+struct GiteaReactionRequest<'a> {
+    content: &'a str,
+}
+
+/// Request to Create a Gitea Comment
+#[derive(Serialize)]
+struct GiteaCommentRequest<'a> {
+    body: &'a str,
+}
+
+/// Request to Create a Gitea Commit Status
+#[derive(Serialize)]
+struct GiteaStatusRequest<'a> {
+    state: &'a str,
+    context: &'a str,
+    description: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_url: Option<&'a str>,
+}
+
+/// Gitea Comment
+#[derive(Deserialize)]
+struct GiteaComment {
+    id: u64,
+    html_url: String,
+}
+
+impl Gitea {
+    /// Create the Gitea Client for the Base URL, with the Token from `GITEA_TOKEN`
+    pub fn from_env(url: &str, owner: &str, repo: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let token = env::var("GITEA_TOKEN")
+            .map_err(|_| "GITEA_TOKEN env variable is required")?;
+        Ok(Self {
+            client: reqwest::Client::new(),
+            api_url: url.trim_end_matches('/').to_string() + "/api/v1",
+            token,
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        })
+    }
+
+    /// Return the API URL for the path in the Repo, like `/pulls`
+    fn repo_url(&self, path: &str) -> String {
+        format!("{}/repos/{}/{}{}", self.api_url, self.owner, self.repo, path)
+    }
+
+    /// Send a GET Request to the Gitea API
+    async fn get<T: DeserializeOwned>(&self, url: &str) -> Result<T, Box<dyn std::error::Error>> {
+        let response = self.client
+            .get(url)
+            .header("Authorization", format!("token {}", self.token))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(response)
+    }
+
+    /// Send GET Requests to the Gitea API for every Page, until the Page is empty.
+    /// Page Size may be capped by the Gitea Server, so a short Page doesn't mean it's the last Page.
+    async fn get_pages<T: DeserializeOwned>(&self, url: &str) -> Result<Vec<T>, Box<dyn std::error::Error>> {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        let mut items: Vec<T> = vec![];
+        for page in 1..=MAX_PAGES {
+            let page_url = format!("{}{}page={}&limit={}", url, separator, page, PAGE_LIMIT);
+            let page_items: Vec<T> = self.get(&page_url).await?;
+            if page_items.is_empty() { break; }
+            items.extend(page_items);
+        }
+        Ok(items)
+    }
+
+    /// Return the IDs of the Repo Labels, since Gitea Labels are updated by ID
+    async fn label_ids(&self, names: &[String]) -> Result<Vec<u64>, Box<dyn std::error::Error>> {
+        let url = self.repo_url("/labels");
+        let labels: Vec<GiteaLabel> = self.get_pages(&url).await?;
+        names
+            .iter()
+            .map(|name| labels
+                .iter()
+                .find(|l| &l.name == name)
+                .map(|l| l.id)
+                .ok_or(format!("Gitea Label not found: {}", name).into())
+            )
+            .collect()
+    }
+
+    /// Send a request with a JSON Body to the Gitea API
+    async fn send<B: Serialize>(&self, method: reqwest::Method, url: &str, body: &B) -> Result<reqwest::Response, Box<dyn std::error::Error>> {
+        let response = self.client
+            .request(method, url)
+            .header("Authorization", format!("token {}", self.token))
+            .json(body)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(response)
+    }
+}
+
+#[async_trait(?Send)]
+impl Forge for Gitea {
+    /// Name of the Forge and Repo
+    fn name(&self) -> String {
+        format!("gitea ({} {}/{})", self.api_url, self.owner, self.repo)
+    }
+
+    /// Fetch the Newest Pull Requests that are Open. Gitea returns the Newest PRs first.
+    async fn list_pulls(&self, count: u8) -> Result<Vec<PullRequest>, Box<dyn std::error::Error>> {
+        let url = self.repo_url(&format!("/pulls?state=open&limit={}", count));
+        let pulls: Vec<GiteaPull> = self.get(&url).await?;
+        Ok(pulls.into_iter().map(to_pull_request).collect())
+    }
+
+    /// Fetch the Pull Request
+    async fn get_pull(&self, pr_id: u64) -> Result<PullRequest, Box<dyn std::error::Error>> {
+        let url = self.repo_url(&format!("/pulls/{}", pr_id));
+        let pull: GiteaPull = self.get(&url).await?;
+        Ok(to_pull_request(pull))
+    }
+
+    /// Fetch the Commits in the Pull Request, page by page
+    async fn list_commits(&self, pr_id: u64) -> Result<Vec<Commit>, Box<dyn std::error::Error>> {
+        let url = self.repo_url(&format!("/pulls/{}/commits", pr_id));
+        let commits: Vec<GiteaCommit> = self.get_pages(&url).await?;
+        Ok(commits.into_iter().map(|c| Commit {
+            sha: c.sha,
+            message: c.commit.message,
+        }).collect())
+    }
+
+    /// Fetch the Files changed by the Pull Request, page by page
+    async fn list_files(&self, pr_id: u64) -> Result<Vec<ChangedFile>, Box<dyn std::error::Error>> {
+        let url = self.repo_url(&format!("/pulls/{}/files", pr_id));
+        let files: Vec<GiteaChangedFile> = self.get_pages(&url).await?;
+        Ok(files.into_iter().map(|f| ChangedFile {
+            path: f.filename,
+            additions: f.additions,
+            deletions: f.deletions,
+        }).collect())
+    }
+
+    /// Fetch the Files changed by the Commit. Gitea doesn't count the Additions and Deletions per Commit File.
+    async fn list_commit_files(&self, sha: &str) -> Result<Vec<ChangedFile>, Box<dyn std::error::Error>> {
+        let url = self.repo_url(&format!("/git/commits/{}?stat=false&verification=false", sha));
+        let commit: GiteaCommitFiles = self.get(&url).await?;
+        Ok(commit.files.unwrap_or_default().into_iter().map(|f| ChangedFile {
+            path: f.filename,
+            additions: f.additions,
+            deletions: f.deletions,
+        }).collect())
+    }
+
+    /// Fetch the Reactions on the Pull Request.
+    /// Gitea Reactions don't have an ID, so we use the Reaction Content as the ID.
+    async fn list_reactions(&self, pr_id: u64) -> Result<Vec<Reaction>, Box<dyn std::error::Error>> {
+        let url = self.repo_url(&format!("/issues/{}/reactions", pr_id));
+        let reactions: Option<Vec<GiteaReaction>> = self.get(&url).await?;
+        Ok(reactions.unwrap_or_default().into_iter().map(|r| {
+            let content = match r.content.as_str() {
+                "rocket" => Some(ReactionContent::Rocket),
+                "eyes"   => Some(ReactionContent::Eyes),
+                _ => None,
+            };
+            Reaction {
+                id: content.map(to_reaction_id).unwrap_or(0),
+                user: r.user.login,
+                content,
+            }
+        }).collect())
+    }
+
+    /// Create a Reaction on the Pull Request
+    async fn create_reaction(&self, pr_id: u64, content: ReactionContent) -> Result<(), Box<dyn std::error::Error>> {
+        let url = self.repo_url(&format!("/issues/{}/reactions", pr_id));
+        let request = GiteaReactionRequest { content: reaction_name(content) };
+        self.send(reqwest::Method::POST, &url, &request).await?;
+        Ok(())
+    }
+
+    /// Delete a Reaction on the Pull Request. Reaction ID is derived from the Reaction Content.
+    async fn delete_reaction(&self, pr_id: u64, reaction_id: u64) -> Result<(), Box<dyn std::error::Error>> {
+        let content = [ReactionContent::Rocket, ReactionContent::Eyes]
+            .into_iter()
+            .find(|c| to_reaction_id(*c) == reaction_id)
+            .ok_or(format!("Unknown Gitea Reaction: {}", reaction_id))?;
+        let url = self.repo_url(&format!("/issues/{}/reactions", pr_id));
+        let request = GiteaReactionRequest { content: reaction_name(content) };
+        self.send(reqwest::Method::DELETE, &url, &request).await?;
+        Ok(())
+    }
+
+    /// Post a Comment on the Pull Request
+    async fn create_comment(&self, pr_id: u64, body: &str) -> Result<Comment, Box<dyn std::error::Error>> {
+        let url = self.repo_url(&format!("/issues/{}/comments", pr_id));
+        let request = GiteaCommentRequest { body };
+        let comment: GiteaComment = self.send(reqwest::Method::POST, &url, &request)
+            .await?
+            .json()
+            .await?;
+        Ok(Comment {
+            id: comment.id,
+            url: comment.html_url,
+        })
+    }
+
+    /// Add the Labels to the Pull Request. Labels must exist in the Repo.
+    async fn add_labels(&self, pr_id: u64, labels: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+        let url = self.repo_url(&format!("/issues/{}/labels", pr_id));
+        let request = GiteaLabelsRequest { labels: self.label_ids(labels).await? };
+        self.send(reqwest::Method::POST, &url, &request).await?;
+        Ok(())
+    }
+
+    /// Remove the Label from the Pull Request
+    async fn remove_label(&self, pr_id: u64, label: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let id = self.label_ids(&[label.to_string()]).await?[0];
+        self.client
+            .delete(self.repo_url(&format!("/issues/{}/labels/{}", pr_id, id)))
+            .header("Authorization", format!("token {}", self.token))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Gitea doesn't support Check Runs
+    async fn create_check_run(&self, check: &CheckRun) -> Result<(), Box<dyn std::error::Error>> {
+        info!("Gitea doesn't support Check Runs, skipping: {}", check.name);
+        Ok(())
+    }
+
+    /// Create or update the Commit Status on the PR Head Commit
+    async fn create_status(&self, status: &CommitStatus) -> Result<(), Box<dyn std::error::Error>> {
+        let url = self.repo_url(&format!("/statuses/{}", status.sha));
+        let request = GiteaStatusRequest {
+            state: match status.state {
+                StatusState::Success => "success",
+                StatusState::Failure => "failure",
+            },
+            context: &status.context,
+            description: &status.description,
+            target_url: status.target_url.as_deref(),
+        };
+        self.send(reqwest::Method::POST, &url, &request).await?;
+        Ok(())
+    }
+}
+
+/// Return the Gitea Name of the Reaction
+fn reaction_name(content: ReactionContent) -> &'static str {
+    match content {
+        ReactionContent::Rocket => "rocket",
+        ReactionContent::Eyes   => "eyes",
+    }
+}
+
+/// Return the Reaction ID for the Reaction Content, since Gitea Reactions don't have an ID
+fn to_reaction_id(content: ReactionContent) -> u64 {
+    match content {
+        ReactionContent::Rocket => 1,
+        ReactionContent::Eyes   => 2,
+    }
+}
+
+/// Convert the Gitea Pull Request to our Pull Request
+fn to_pull_request(pr: GiteaPull) -> PullRequest {
+    PullRequest {
+        number: pr.number,
+        url: pr.html_url,
+        title: pr.title,
+        body: pr.body.unwrap_or_default(),
+        author: pr.user.map(|u| u.login).unwrap_or_default(),
+        open: pr.state == "open",
+        comments: pr.comments,
+        labels: pr.labels.into_iter().map(|l| l.name).collect(),
+        head_sha: pr.head.sha,
+    }
+}
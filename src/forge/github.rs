@@ -0,0 +1,269 @@
+//! GitHub as Code Forge, via octocrab
+
+use std::env;
+use async_trait::async_trait;
+use octocrab::{
+    models::{self, reactions, IssueState},
+    params::{self, checks::{CheckRunConclusion, CheckRunOutput, CheckRunStatus}},
+    Octocrab
+};
+use super::{ChangedFile, CheckRun, Comment, Commit, CommitStatus, Conclusion, Forge, PullRequest, Reaction, ReactionContent, StatusState};
+
+/// Max Length of the Check Run Summary
+const MAX_SUMMARY: usize = 65535;
+
+/// Max Pages of Changed Files (100 per page). GitHub returns up to 3000 files for a PR.
+const MAX_FILE_PAGES: u32 = 30;
+
+/// Max Pages of Commits (100 per page). GitHub returns up to 250 commits for a PR.
+const MAX_COMMIT_PAGES: u32 = 3;
+
+/// GitHub Repo
+pub struct GitHub {
+    /// GitHub Client
+    octocrab: Octocrab,
+
+    /// Owner of the GitHub Repo (`apache`)
+    owner: String,
+
+    /// Name of the GitHub Repo (`nuttx` or `nuttx-apps`)
+    repo: String,
+}
+
+impl GitHub {
+    /// Create the GitHub Client with the Token from `GITHUB_TOKEN`.
+    /// The GitHub Client may be shared by multiple Repos.
+    pub fn client_from_env() -> Result<Octocrab, Box<dyn std::error::Error>> {
+        let token = env::var("GITHUB_TOKEN")
+            .map_err(|_| "GITHUB_TOKEN env variable is required")?;
+        let octocrab = Octocrab::builder()
+            .personal_token(token)
+            .build()?;
+        Ok(octocrab)
+    }
+
+    /// Create the GitHub Repo with a GitHub Client
+    pub fn new(octocrab: Octocrab, owner: &str, repo: &str) -> Self {
+        Self {
+            octocrab,
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Forge for GitHub {
+    /// Name of the Forge and Repo
+    fn name(&self) -> String {
+        format!("github ({}/{})", self.owner, self.repo)
+    }
+
+    /// Fetch the Newest Pull Requests that are Open
+    async fn list_pulls(&self, count: u8) -> Result<Vec<PullRequest>, Box<dyn std::error::Error>> {
+        let pr_list = self.octocrab
+            .pulls(&self.owner, &self.repo)
+            .list()
+            .state(params::State::Open)
+            .sort(params::pulls::Sort::Created)
+            .direction(params::Direction::Descending)
+            .per_page(count)
+            .send()
+            .await?;
+        Ok(pr_list.items.into_iter().map(to_pull_request).collect())
+    }
+
+    /// Fetch the Pull Request
+    async fn get_pull(&self, pr_id: u64) -> Result<PullRequest, Box<dyn std::error::Error>> {
+        let pr = self.octocrab
+            .pulls(&self.owner, &self.repo)
+            .get(pr_id)
+            .await?;
+        Ok(to_pull_request(pr))
+    }
+
+    /// Fetch the Commits in the Pull Request (up to 250), page by page
+    async fn list_commits(&self, pr_id: u64) -> Result<Vec<Commit>, Box<dyn std::error::Error>> {
+        let route = format!("/repos/{}/{}/pulls/{}/commits", self.owner, self.repo, pr_id);
+        let mut commits: Vec<Commit> = vec![];
+        for page in 1..=MAX_COMMIT_PAGES {
+            let entries: Vec<models::repos::RepoCommit> = self.octocrab
+                .get(&route, Some(&[("per_page", 100), ("page", page)]))
+                .await?;
+            let count = entries.len();
+            commits.extend(entries.into_iter().map(|c| Commit {
+                sha: c.sha,
+                message: c.commit.message,
+            }));
+            if count < 100 { break; }
+        }
+        Ok(commits)
+    }
+
+    /// Fetch the Files changed by the Pull Request (up to 3000), page by page
+    async fn list_files(&self, pr_id: u64) -> Result<Vec<ChangedFile>, Box<dyn std::error::Error>> {
+        let route = format!("/repos/{}/{}/pulls/{}/files", self.owner, self.repo, pr_id);
+        let mut files: Vec<ChangedFile> = vec![];
+        for page in 1..=MAX_FILE_PAGES {
+            let entries: Vec<models::repos::DiffEntry> = self.octocrab
+                .get(&route, Some(&[("per_page", 100), ("page", page)]))
+                .await?;
+            let count = entries.len();
+            files.extend(entries.into_iter().map(|f| ChangedFile {
+                path: f.filename,
+                additions: f.additions,
+                deletions: f.deletions,
+            }));
+            if count < 100 { break; }
+        }
+        Ok(files)
+    }
+
+    /// Fetch the Files changed by the Commit (up to 300)
+    async fn list_commit_files(&self, sha: &str) -> Result<Vec<ChangedFile>, Box<dyn std::error::Error>> {
+        let route = format!("/repos/{}/{}/commits/{}", self.owner, self.repo, sha);
+        let commit: models::repos::RepoCommit = self.octocrab
+            .get(route, None::<&()>)
+            .await?;
+        Ok(commit.files.unwrap_or_default().into_iter().map(|f| ChangedFile {
+            path: f.filename,
+            additions: f.additions,
+            deletions: f.deletions,
+        }).collect())
+    }
+
+    /// Fetch the Reactions on the Pull Request
+    async fn list_reactions(&self, pr_id: u64) -> Result<Vec<Reaction>, Box<dyn std::error::Error>> {
+        let reactions = self.octocrab
+            .issues(&self.owner, &self.repo)
+            .list_reactions(pr_id)
+            .send()
+            .await?;
+        Ok(reactions.items.into_iter().map(|r| Reaction {
+            id: r.id.0,
+            user: r.user.login,
+            content: match r.content {
+                reactions::ReactionContent::Rocket => Some(ReactionContent::Rocket),
+                reactions::ReactionContent::Eyes   => Some(ReactionContent::Eyes),
+                _ => None,
+            },
+        }).collect())
+    }
+
+    /// Create a Reaction on the Pull Request
+    async fn create_reaction(&self, pr_id: u64, content: ReactionContent) -> Result<(), Box<dyn std::error::Error>> {
+        let content = match content {
+            ReactionContent::Rocket => reactions::ReactionContent::Rocket,
+            ReactionContent::Eyes   => reactions::ReactionContent::Eyes,
+        };
+        self.octocrab
+            .issues(&self.owner, &self.repo)
+            .create_reaction(pr_id, content)
+            .await?;
+        Ok(())
+    }
+
+    /// Delete a Reaction on the Pull Request
+    async fn delete_reaction(&self, pr_id: u64, reaction_id: u64) -> Result<(), Box<dyn std::error::Error>> {
+        self.octocrab
+            .issues(&self.owner, &self.repo)
+            .delete_reaction(pr_id, reaction_id)
+            .await?;
+        Ok(())
+    }
+
+    /// Post a Comment on the Pull Request
+    async fn create_comment(&self, pr_id: u64, body: &str) -> Result<Comment, Box<dyn std::error::Error>> {
+        let comment = self.octocrab
+            .issues(&self.owner, &self.repo)
+            .create_comment(pr_id, body)
+            .await?;
+        Ok(Comment {
+            id: comment.id.0,
+            url: comment.html_url.to_string(),
+        })
+    }
+
+    /// Add the Labels to the Pull Request
+    async fn add_labels(&self, pr_id: u64, labels: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+        self.octocrab
+            .issues(&self.owner, &self.repo)
+            .add_labels(pr_id, labels)
+            .await?;
+        Ok(())
+    }
+
+    /// Remove the Label from the Pull Request
+    async fn remove_label(&self, pr_id: u64, label: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.octocrab
+            .issues(&self.owner, &self.repo)
+            .remove_label(pr_id, label)
+            .await?;
+        Ok(())
+    }
+
+    /// Create a completed Check Run on the PR Head Commit, without Annotations.
+    /// GitHub allows Check Runs only for GitHub Apps, not Personal Access Tokens.
+    async fn create_check_run(&self, check: &CheckRun) -> Result<(), Box<dyn std::error::Error>> {
+        let conclusion = match check.conclusion {
+            Conclusion::Success => CheckRunConclusion::Success,
+            Conclusion::Neutral => CheckRunConclusion::Neutral,
+            Conclusion::Failure => CheckRunConclusion::Failure,
+        };
+        let mut summary = check.summary.clone();
+        if summary.len() > MAX_SUMMARY {
+            let mut end = MAX_SUMMARY;
+            while !summary.is_char_boundary(end) { end -= 1; }
+            summary.truncate(end);
+        }
+        self.octocrab
+            .checks(&self.owner, &self.repo)
+            .create_check_run(&check.name, &check.head_sha)
+            .status(CheckRunStatus::Completed)
+            .conclusion(conclusion)
+            .output(CheckRunOutput {
+                title: check.title.clone(),
+                summary,
+                text: None,
+                annotations: vec![],
+                images: vec![],
+            })
+            .send()
+            .await?;
+        Ok(())
+    }
+
+    /// Create or update the Commit Status on the PR Head Commit
+    async fn create_status(&self, status: &CommitStatus) -> Result<(), Box<dyn std::error::Error>> {
+        let state = match status.state {
+            StatusState::Success => models::StatusState::Success,
+            StatusState::Failure => models::StatusState::Failure,
+        };
+        let repos = self.octocrab
+            .repos(&self.owner, &self.repo);
+        let mut request = repos
+            .create_status(status.sha.clone(), state)
+            .context(status.context.clone())
+            .description(status.description.clone());
+        if let Some(url) = &status.target_url {
+            request = request.target(url.clone());
+        }
+        request.send().await?;
+        Ok(())
+    }
+}
+
+/// Convert the octocrab Pull Request to our Pull Request
+fn to_pull_request(pr: models::pulls::PullRequest) -> PullRequest {
+    PullRequest {
+        number: pr.number,
+        url: pr.html_url.map(|u| u.to_string()).unwrap_or(pr.url),
+        title: pr.title.unwrap_or_default(),
+        body: pr.body.unwrap_or_default(),
+        author: pr.user.map(|u| u.login).unwrap_or_default(),
+        open: pr.state == Some(IssueState::Open),
+        comments: pr.comments.unwrap_or(0),
+        labels: pr.labels.unwrap_or_default().into_iter().map(|l| l.name).collect(),
+        head_sha: pr.head.sha,
+    }
+}
@@ -0,0 +1,400 @@
+//! GitLab as Code Forge, via the GitLab REST API.
+//! Merge Requests are reviewed like PRs. Award Emoji are used as PR Reactions, Notes as PR Comments.
+//! See https://docs.gitlab.com/ee/api/merge_requests.html
+
+use std::env;
+use async_trait::async_trait;
+use log::info;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use super::{ChangedFile, CheckRun, Comment, Commit, CommitStatus, Forge, PullRequest, Reaction, ReactionContent, StatusState};
+
+/// Default Base URL of the GitLab Server
+pub const DEFAULT_URL: &str = "https://gitlab.com";
+
+/// Items per Page for the GitLab API (max 100)
+const PER_PAGE: usize = 100;
+
+/// Max Pages fetched for Commits and Diffs
+const MAX_PAGES: usize = 30;
+
+/// GitLab Project
+pub struct GitLab {
+    /// HTTP Client
+    client: reqwest::Client,
+
+    /// Base URL of the GitLab Server, like `https://gitlab.com`
+    url: String,
+
+    /// Base URL of the GitLab API, like `https://gitlab.com/api/v4`
+    api_url: String,
+
+    /// Access Token from `GITLAB_TOKEN`
+    token: String,
+
+    /// Path of the GitLab Project, like `owner/repo` or `group/subgroup/repo`
+    project: String,
+}
+
+/// GitLab User
+#[derive(Deserialize)]
+struct GitLabUser {
+    username: String,
+}
+
+/// GitLab Merge Request
+#[derive(Deserialize)]
+struct GitLabMergeRequest {
+    iid: u64,
+    web_url: String,
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    author: Option<GitLabUser>,
+    state: String,
+    #[serde(default)]
+    user_notes_count: u64,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    sha: Option<String>,
+}
+
+/// GitLab Commit
+#[derive(Deserialize)]
+struct GitLabCommit {
+    id: String,
+    message: String,
+}
+
+/// GitLab Diff of a Changed File
+#[derive(Deserialize)]
+struct GitLabDiff {
+    new_path: String,
+    #[serde(default)]
+    diff: String,
+}
+
+/// GitLab Award Emoji
+#[derive(Deserialize)]
+struct GitLabAwardEmoji {
+    id: u64,
+    name: String,
+    user: GitLabUser,
+}
+
+/// Request to Create a GitLab Award Emoji
+#[derive(Serialize)]
+struct GitLabAwardEmojiRequest<'a> {
+    name: &'a str,
+}
+
+/// Request to Create a GitLab Note
+#[derive(Serialize)]
+struct GitLabNoteRequest<'a> {
+    body: &'a str,
+}
+
+/// Request to Add or Remove the GitLab Labels (comma-separated)
+#[derive(Serialize)]
+struct GitLabLabelsRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    add_labels: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remove_labels: Option<String>,
+}
+
+/// Request to Create a GitLab Commit Status
+#[derive(Serialize)]
+struct GitLabStatusRequest<'a> {
+    state: &'a str,
+    name: &'a str,
+    description: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_url: Option<&'a str>,
+}
+
+/// GitLab Status of a Commit
+#[derive(Deserialize)]
+struct GitLabStatus {}
+
+/// GitLab Note
+#[derive(Deserialize)]
+struct GitLabNote {
+    id: u64,
+}
+
+impl GitLab {
+    /// Create the GitLab Client for the Base URL, with the Token from `GITLAB_TOKEN`
+    pub fn from_env(url: &str, owner: &str, repo: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let token = env::var("GITLAB_TOKEN")
+            .map_err(|_| "GITLAB_TOKEN env variable is required")?;
+        let url = url.trim_end_matches('/').to_string();
+        Ok(Self {
+            client: reqwest::Client::new(),
+            api_url: url.clone() + "/api/v4",
+            url,
+            token,
+            project: format!("{}/{}", owner, repo),
+        })
+    }
+
+    /// Return the API URL for the path in the Merge Request, like `/commits`
+    fn mr_url(&self, mr_id: u64, path: &str) -> String {
+        format!("{}/projects/{}/merge_requests/{}{}",
+            self.api_url, self.project.replace('/', "%2F"), mr_id, path)
+    }
+
+    /// Send a GET Request to the GitLab API
+    async fn get<T: DeserializeOwned>(&self, url: &str) -> Result<T, Box<dyn std::error::Error>> {
+        let response = self.client
+            .get(url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(response)
+    }
+
+    /// Send GET Requests to the GitLab API for every Page, until the Page is not full
+    async fn get_pages<T: DeserializeOwned>(&self, url: &str) -> Result<Vec<T>, Box<dyn std::error::Error>> {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        let mut items: Vec<T> = vec![];
+        for page in 1..=MAX_PAGES {
+            let page_url = format!("{}{}page={}&per_page={}", url, separator, page, PER_PAGE);
+            let page_items: Vec<T> = self.get(&page_url).await?;
+            let count = page_items.len();
+            items.extend(page_items);
+            if count < PER_PAGE { break; }
+        }
+        Ok(items)
+    }
+
+    /// Update the Labels of the Merge Request
+    async fn update_labels(&self, mr_id: u64, request: &GitLabLabelsRequest) -> Result<(), Box<dyn std::error::Error>> {
+        self.client
+            .put(self.mr_url(mr_id, ""))
+            .header("PRIVATE-TOKEN", &self.token)
+            .json(request)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Send a POST Request with a JSON Body to the GitLab API
+    async fn post<B: Serialize, T: DeserializeOwned>(&self, url: &str, body: &B) -> Result<T, Box<dyn std::error::Error>> {
+        let response = self.client
+            .post(url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .json(body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(response)
+    }
+}
+
+#[async_trait(?Send)]
+impl Forge for GitLab {
+    /// Name of the Forge and Project
+    fn name(&self) -> String {
+        format!("gitlab ({} {})", self.api_url, self.project)
+    }
+
+    /// Fetch the Newest Merge Requests that are Open
+    async fn list_pulls(&self, count: u8) -> Result<Vec<PullRequest>, Box<dyn std::error::Error>> {
+        let url = format!("{}/projects/{}/merge_requests?state=opened&order_by=created_at&sort=desc&per_page={}",
+            self.api_url, self.project.replace('/', "%2F"), count);
+        let mrs: Vec<GitLabMergeRequest> = self.get(&url).await?;
+        Ok(mrs.into_iter().map(to_pull_request).collect())
+    }
+
+    /// Fetch the Merge Request
+    async fn get_pull(&self, pr_id: u64) -> Result<PullRequest, Box<dyn std::error::Error>> {
+        let mr: GitLabMergeRequest = self.get(&self.mr_url(pr_id, "")).await?;
+        Ok(to_pull_request(mr))
+    }
+
+    /// Fetch the Commits in the Merge Request, page by page
+    async fn list_commits(&self, pr_id: u64) -> Result<Vec<Commit>, Box<dyn std::error::Error>> {
+        let commits: Vec<GitLabCommit> = self.get_pages(&self.mr_url(pr_id, "/commits")).await?;
+        Ok(commits.into_iter().map(|c| Commit {
+            sha: c.id,
+            message: c.message,
+        }).collect())
+    }
+
+    /// Fetch the Files changed by the Merge Request, page by page.
+    /// GitLab doesn't count the Lines added and deleted, so we count them in the Diff.
+    async fn list_files(&self, pr_id: u64) -> Result<Vec<ChangedFile>, Box<dyn std::error::Error>> {
+        let diffs: Vec<GitLabDiff> = self.get_pages(&self.mr_url(pr_id, "/diffs")).await?;
+        Ok(diffs.into_iter().map(to_changed_file).collect())
+    }
+
+    /// Fetch the Files changed by the Commit, page by page
+    async fn list_commit_files(&self, sha: &str) -> Result<Vec<ChangedFile>, Box<dyn std::error::Error>> {
+        let url = format!("{}/projects/{}/repository/commits/{}/diff",
+            self.api_url, self.project.replace('/', "%2F"), sha);
+        let diffs: Vec<GitLabDiff> = self.get_pages(&url).await?;
+        Ok(diffs.into_iter().map(to_changed_file).collect())
+    }
+
+    /// Fetch the Award Emoji on the Merge Request
+    async fn list_reactions(&self, pr_id: u64) -> Result<Vec<Reaction>, Box<dyn std::error::Error>> {
+        let emojis: Vec<GitLabAwardEmoji> = self.get(&self.mr_url(pr_id, "/award_emoji")).await?;
+        Ok(emojis.into_iter().map(|e| Reaction {
+            id: e.id,
+            user: e.user.username,
+            content: match e.name.as_str() {
+                "rocket" => Some(ReactionContent::Rocket),
+                "eyes"   => Some(ReactionContent::Eyes),
+                _ => None,
+            },
+        }).collect())
+    }
+
+    /// Award an Emoji on the Merge Request
+    async fn create_reaction(&self, pr_id: u64, content: ReactionContent) -> Result<(), Box<dyn std::error::Error>> {
+        let name = match content {
+            ReactionContent::Rocket => "rocket",
+            ReactionContent::Eyes   => "eyes",
+        };
+        let _emoji: GitLabAwardEmoji = self.post(
+            &self.mr_url(pr_id, "/award_emoji"),
+            &GitLabAwardEmojiRequest { name }
+        ).await?;
+        Ok(())
+    }
+
+    /// Delete the Award Emoji on the Merge Request
+    async fn delete_reaction(&self, pr_id: u64, reaction_id: u64) -> Result<(), Box<dyn std::error::Error>> {
+        self.client
+            .delete(self.mr_url(pr_id, &format!("/award_emoji/{}", reaction_id)))
+            .header("PRIVATE-TOKEN", &self.token)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Post a Note on the Merge Request
+    async fn create_comment(&self, pr_id: u64, body: &str) -> Result<Comment, Box<dyn std::error::Error>> {
+        let note: GitLabNote = self.post(
+            &self.mr_url(pr_id, "/notes"),
+            &GitLabNoteRequest { body }
+        ).await?;
+        Ok(Comment {
+            id: note.id,
+            url: format!("{}/{}/-/merge_requests/{}#note_{}", self.url, self.project, pr_id, note.id),
+        })
+    }
+
+    /// Add the Labels to the Merge Request
+    async fn add_labels(&self, pr_id: u64, labels: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_labels(pr_id, &GitLabLabelsRequest {
+            add_labels: Some(labels.join(",")),
+            remove_labels: None,
+        }).await
+    }
+
+    /// Remove the Label from the Merge Request
+    async fn remove_label(&self, pr_id: u64, label: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_labels(pr_id, &GitLabLabelsRequest {
+            add_labels: None,
+            remove_labels: Some(label.to_string()),
+        }).await
+    }
+
+    /// GitLab doesn't support Check Runs
+    async fn create_check_run(&self, check: &CheckRun) -> Result<(), Box<dyn std::error::Error>> {
+        info!("GitLab doesn't support Check Runs, skipping: {}", check.name);
+        Ok(())
+    }
+
+    /// Create or update the Commit Status on the PR Head Commit
+    async fn create_status(&self, status: &CommitStatus) -> Result<(), Box<dyn std::error::Error>> {
+        let url = format!("{}/projects/{}/statuses/{}",
+            self.api_url, self.project.replace('/', "%2F"), status.sha);
+        let request = GitLabStatusRequest {
+            state: match status.state {
+                StatusState::Success => "success",
+                StatusState::Failure => "failed",
+            },
+            name: &status.context,
+            description: &status.description,
+            target_url: status.target_url.as_deref(),
+        };
+        let _status: GitLabStatus = self.post(&url, &request).await?;
+        Ok(())
+    }
+}
+
+/// Convert the GitLab Diff to our Changed File, counting the Lines added and deleted.
+/// GitLab Diff has no File Headers (`+++` / `---`), it starts at the Hunk Header (`@@`).
+/// So every Line that starts with `+` or `-` is counted, like `++i;` and `-- comment`.
+fn to_changed_file(diff: GitLabDiff) -> ChangedFile {
+    let count = |prefix: char| diff.diff
+        .lines()
+        .filter(|l| l.starts_with(prefix))
+        .count() as u64;
+    ChangedFile {
+        additions: count('+'),
+        deletions: count('-'),
+        path: diff.new_path,
+    }
+}
+
+/// Convert the GitLab Merge Request to our Pull Request
+fn to_pull_request(mr: GitLabMergeRequest) -> PullRequest {
+    PullRequest {
+        number: mr.iid,
+        url: mr.web_url,
+        title: mr.title,
+        body: mr.description.unwrap_or_default(),
+        author: mr.author.map(|u| u.username).unwrap_or_default(),
+        open: mr.state == "opened",
+        comments: mr.user_notes_count,
+        labels: mr.labels,
+        head_sha: mr.sha.unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_file() {
+        // Hunk adds `++i;` and deletes `-- comment`, which look like File Headers
+        let diff: GitLabDiff = serde_json::from_value(serde_json::json!({
+            "new_path": "drivers/timers/timer.c",
+            "diff": concat!(
+                "@@ -10,5 +10,6 @@ int timer(void)\n",
+                " {\n",
+                "-  i++;\n",
+                "+  ++i;\n",
+                "+++i;\n",
+                "--- comment\n",
+                "-- comment\n",
+                "   return i;\n",
+                "@@ -40 +41 @@\n",
+                "-}\n",
+                "+}\n",
+                "\\ No newline at end of file\n",
+            ),
+        })).unwrap();
+        let file = to_changed_file(diff);
+        assert_eq!(file.path, "drivers/timers/timer.c");
+        assert_eq!(file.additions, 3);
+        assert_eq!(file.deletions, 4);
+
+        // Binary File has no Diff
+        let diff: GitLabDiff = serde_json::from_value(serde_json::json!({ "new_path": "logo.png" })).unwrap();
+        let file = to_changed_file(diff);
+        assert_eq!((file.additions, file.deletions), (0, 0));
+    }
+}
@@ -0,0 +1,222 @@
+//! In-Memory Forge for Testing, loaded from a JSON Fixture.
+//! Runs the whole pipeline offline: Reactions and Comments are kept in memory and logged.
+//!
+//! Fixture looks like:
+//! `{ "pulls": [ { "pull": { "number": 1, "open": true, "labels": ["Size: M"], "body": "..." }, "commits": [ { "sha": "...", "message": "..." } ], "files": [ { "path": "...", "additions": 1, "deletions": 0 } ] } ] }`
+//! Files changed by each Commit may be set in `"commit_files": { "<sha>": [ ... ] }`, otherwise the PR Files are used.
+
+use std::{cell::RefCell, collections::BTreeMap, fs, rc::Rc};
+use async_trait::async_trait;
+use log::info;
+use serde::{Deserialize, Serialize};
+use super::{ChangedFile, CheckRun, Comment, Commit, CommitStatus, Forge, PullRequest, Reaction, ReactionContent};
+
+/// Default Login of the User who creates the Reactions and Comments
+const BOT_LOGIN: &str = "nuttxpr";
+
+/// JSON Fixture for the In-Memory Forge
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Fixture {
+    /// Pull Requests in the Forge, Newest First
+    pub pulls: Vec<MemoryPull>,
+
+    /// Check Runs created on the PR Head Commits
+    pub checks: Vec<CheckRun>,
+
+    /// Commit Statuses on the PR Head Commits. Newer Status replaces the older Status with the same SHA and Context.
+    pub statuses: Vec<CommitStatus>,
+}
+
+/// Pull Request in the In-Memory Forge
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MemoryPull {
+    /// Pull Request
+    pub pull: PullRequest,
+
+    /// Commits in the Pull Request
+    pub commits: Vec<Commit>,
+
+    /// Files changed by the Pull Request
+    pub files: Vec<ChangedFile>,
+
+    /// Files changed by each Commit SHA. If omitted: Same as the Files changed by the Pull Request.
+    pub commit_files: BTreeMap<String, Vec<ChangedFile>>,
+
+    /// Reactions on the Pull Request
+    pub reactions: Vec<Reaction>,
+
+    /// Comments posted on the Pull Request
+    pub comments: Vec<String>,
+}
+
+/// In-Memory Forge
+pub struct MemoryForge {
+    /// Pull Requests, Commits, Reactions and Comments. Shared with the Tests, which inspect the Fixture.
+    fixture: Rc<RefCell<Fixture>>,
+
+    /// Next ID for Reactions and Comments
+    next_id: RefCell<u64>,
+
+    /// Login of the User who creates the Reactions and Comments
+    login: String,
+}
+
+impl MemoryForge {
+    /// Create the In-Memory Forge with the Fixture
+    pub fn new(fixture: Fixture) -> Self {
+        Self {
+            fixture: Rc::new(RefCell::new(fixture)),
+            next_id: RefCell::new(1),
+            login: BOT_LOGIN.to_string(),
+        }
+    }
+
+    /// Create the Reactions and Comments as the User, like `github-actions[bot]`
+    #[cfg(test)]
+    pub fn with_login(mut self, login: &str) -> Self {
+        self.login = login.to_string();
+        self
+    }
+
+    /// Create the In-Memory Forge with the Fixture loaded from the JSON File
+    pub fn from_fixture(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let json = fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read Fixture {}: {}", path, e))?;
+        let fixture: Fixture = serde_json::from_str(&json)
+            .map_err(|e| format!("Invalid Fixture {}: {}", path, e))?;
+        Ok(Self::new(fixture))
+    }
+
+    /// Return the Fixture, which is updated as the Forge is called
+    #[cfg(test)]
+    pub fn fixture(&self) -> Rc<RefCell<Fixture>> {
+        self.fixture.clone()
+    }
+
+    /// Allocate the next ID for Reactions and Comments
+    fn next_id(&self) -> u64 {
+        let mut next_id = self.next_id.borrow_mut();
+        *next_id += 1;
+        *next_id - 1
+    }
+
+    /// Apply the function to the Pull Request
+    fn with_pull<T>(&self, pr_id: u64, f: impl FnOnce(&mut MemoryPull) -> T) -> Result<T, Box<dyn std::error::Error>> {
+        let mut fixture = self.fixture.borrow_mut();
+        let pull = fixture.pulls
+            .iter_mut()
+            .find(|p| p.pull.number == pr_id)
+            .ok_or(format!("PR not found: {}", pr_id))?;
+        Ok(f(pull))
+    }
+}
+
+#[async_trait(?Send)]
+impl Forge for MemoryForge {
+    /// Name of the Forge
+    fn name(&self) -> String {
+        "memory".to_string()
+    }
+
+    /// Fetch the Newest Pull Requests that are Open
+    async fn list_pulls(&self, count: u8) -> Result<Vec<PullRequest>, Box<dyn std::error::Error>> {
+        Ok(self.fixture.borrow().pulls
+            .iter()
+            .filter(|p| p.pull.open)
+            .take(count as usize)
+            .map(|p| p.pull.clone())
+            .collect())
+    }
+
+    /// Fetch the Pull Request
+    async fn get_pull(&self, pr_id: u64) -> Result<PullRequest, Box<dyn std::error::Error>> {
+        self.with_pull(pr_id, |p| p.pull.clone())
+    }
+
+    /// Fetch the Commits in the Pull Request
+    async fn list_commits(&self, pr_id: u64) -> Result<Vec<Commit>, Box<dyn std::error::Error>> {
+        self.with_pull(pr_id, |p| p.commits.clone())
+    }
+
+    /// Fetch the Files changed by the Pull Request
+    async fn list_files(&self, pr_id: u64) -> Result<Vec<ChangedFile>, Box<dyn std::error::Error>> {
+        self.with_pull(pr_id, |p| p.files.clone())
+    }
+
+    /// Fetch the Files changed by the Commit
+    async fn list_commit_files(&self, sha: &str) -> Result<Vec<ChangedFile>, Box<dyn std::error::Error>> {
+        let fixture = self.fixture.borrow();
+        let pull = fixture.pulls
+            .iter()
+            .find(|p| p.commits.iter().any(|c| c.sha == sha))
+            .ok_or(format!("Commit not found: {}", sha))?;
+        Ok(pull.commit_files.get(sha).unwrap_or(&pull.files).clone())
+    }
+
+    /// Fetch the Reactions on the Pull Request
+    async fn list_reactions(&self, pr_id: u64) -> Result<Vec<Reaction>, Box<dyn std::error::Error>> {
+        self.with_pull(pr_id, |p| p.reactions.clone())
+    }
+
+    /// Create a Reaction on the Pull Request
+    async fn create_reaction(&self, pr_id: u64, content: ReactionContent) -> Result<(), Box<dyn std::error::Error>> {
+        let id = self.next_id();
+        info!("Memory Forge: Create Reaction {:?} on PR {}", content, pr_id);
+        self.with_pull(pr_id, |p| p.reactions.push(Reaction {
+            id,
+            user: self.login.clone(),
+            content: Some(content),
+        }))
+    }
+
+    /// Delete a Reaction on the Pull Request
+    async fn delete_reaction(&self, pr_id: u64, reaction_id: u64) -> Result<(), Box<dyn std::error::Error>> {
+        info!("Memory Forge: Delete Reaction {} on PR {}", reaction_id, pr_id);
+        self.with_pull(pr_id, |p| p.reactions.retain(|r| r.id != reaction_id))
+    }
+
+    /// Post a Comment on the Pull Request
+    async fn create_comment(&self, pr_id: u64, body: &str) -> Result<Comment, Box<dyn std::error::Error>> {
+        let id = self.next_id();
+        info!("Memory Forge: Comment on PR {}:\n{}", pr_id, body);
+        self.with_pull(pr_id, |p| {
+            p.comments.push(body.to_string());
+            p.pull.comments += 1;
+            Comment { id, url: format!("memory://pulls/{}#comment-{}", pr_id, id) }
+        })
+    }
+
+    /// Add the Labels to the Pull Request
+    async fn add_labels(&self, pr_id: u64, labels: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+        info!("Memory Forge: Add Labels {:?} on PR {}", labels, pr_id);
+        self.with_pull(pr_id, |p| for label in labels {
+            if !p.pull.labels.contains(label) {
+                p.pull.labels.push(label.clone());
+            }
+        })
+    }
+
+    /// Remove the Label from the Pull Request
+    async fn remove_label(&self, pr_id: u64, label: &str) -> Result<(), Box<dyn std::error::Error>> {
+        info!("Memory Forge: Remove Label {:?} on PR {}", label, pr_id);
+        self.with_pull(pr_id, |p| p.pull.labels.retain(|l| l != label))
+    }
+
+    /// Create a completed Check Run on the PR Head Commit
+    async fn create_check_run(&self, check: &CheckRun) -> Result<(), Box<dyn std::error::Error>> {
+        info!("Memory Forge: Check Run {:?} on {}: {:?}\n{}", check.name, check.head_sha, check.conclusion, check.summary);
+        self.fixture.borrow_mut().checks.push(check.clone());
+        Ok(())
+    }
+
+    /// Create or update the Commit Status on the PR Head Commit
+    async fn create_status(&self, status: &CommitStatus) -> Result<(), Box<dyn std::error::Error>> {
+        info!("Memory Forge: Commit Status {:?} on {}: {:?} {:?} {:?}", status.context, status.sha, status.state, status.description, status.target_url);
+        let mut fixture = self.fixture.borrow_mut();
+        fixture.statuses.retain(|s| s.sha != status.sha || s.context != status.context);
+        fixture.statuses.push(status.clone());
+        Ok(())
+    }
+}
@@ -0,0 +1,301 @@
+//! Code Forges that host the PRs: GitHub, Gitea / Forgejo / Codeberg, GitLab, ...
+//!   List the Open PRs, fetch the PR and its Commits and Changed Files,
+//!   manage the PR Reactions and PR Labels, post the PR Comment, the Check Run and the Commit Status
+
+mod dry_run;
+mod gitea;
+mod github;
+mod gitlab;
+mod memory;
+
+use async_trait::async_trait;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+pub use dry_run::DryRun;
+pub use gitea::Gitea;
+pub use github::GitHub;
+pub use gitlab::GitLab;
+pub use memory::MemoryForge;
+#[cfg(test)]
+pub use memory::{Fixture, MemoryPull};
+
+/// Pull Request fetched from the Forge
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PullRequest {
+    /// PR Number, like `13552`
+    pub number: u64,
+
+    /// Web URL of the PR
+    pub url: String,
+
+    /// PR Title
+    pub title: String,
+
+    /// PR Body, empty if not provided
+    pub body: String,
+
+    /// Login of the PR Author
+    pub author: String,
+
+    /// True if the PR is Open
+    pub open: bool,
+
+    /// Number of PR Comments
+    pub comments: u64,
+
+    /// Names of the PR Labels, like `Size: XS`
+    pub labels: Vec<String>,
+
+    /// Commit SHA of the PR Head
+    pub head_sha: String,
+}
+
+/// Commit in a Pull Request
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Commit {
+    /// Commit SHA
+    pub sha: String,
+
+    /// Commit Message: "title\n\nbody"
+    pub message: String,
+}
+
+/// File changed by a Pull Request
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChangedFile {
+    /// Path of the File, like `arch/risc-v/src/common/riscv_exception.c`
+    pub path: String,
+
+    /// Number of Lines added
+    pub additions: u64,
+
+    /// Number of Lines deleted
+    pub deletions: u64,
+}
+
+/// Reactions used by the Bot to count the retries
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReactionContent {
+    Rocket,
+    Eyes,
+}
+
+/// Reaction on a Pull Request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reaction {
+    /// Reaction ID
+    pub id: u64,
+
+    /// Login of the User who created the Reaction
+    pub user: String,
+
+    /// Reaction Content. None if it's not used by the Bot (like `+1`).
+    pub content: Option<ReactionContent>,
+}
+
+/// Comment posted on a Pull Request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    /// Comment ID
+    pub id: u64,
+
+    /// Web URL of the Comment
+    pub url: String,
+}
+
+/// Conclusion of a Check Run
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Conclusion {
+    Success,
+    Neutral,
+    Failure,
+}
+
+/// Check Run on the PR Head Commit, shown next to the CI Checks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckRun {
+    /// Name of the Check Run, like `PR Template`
+    pub name: String,
+
+    /// Commit SHA of the PR Head
+    pub head_sha: String,
+
+    /// Conclusion of the Check Run
+    pub conclusion: Conclusion,
+
+    /// Title of the Check Run Output
+    pub title: String,
+
+    /// Summary of the Check Run Output (Markdown)
+    pub summary: String,
+}
+
+/// State of a Commit Status
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusState {
+    Success,
+    Failure,
+}
+
+/// Commit Status on the PR Head Commit, for Branch Protection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitStatus {
+    /// Commit SHA of the PR Head
+    pub sha: String,
+
+    /// State of the Commit Status
+    pub state: StatusState,
+
+    /// Context of the Commit Status, like `nuttx-pr-bot/template`
+    pub context: String,
+
+    /// Short Description of the Commit Status
+    pub description: String,
+
+    /// Link to the PR Comment, if any
+    pub target_url: Option<String>,
+}
+
+/// Code Forge that hosts the PRs
+#[async_trait(?Send)]
+pub trait Forge {
+    /// Name of the Forge and Repo, for logging
+    fn name(&self) -> String;
+
+    /// True if the Forge is a Dry Run that touches nothing
+    fn is_dry_run(&self) -> bool {
+        false
+    }
+
+    /// Fetch the Newest Pull Requests that are Open
+    async fn list_pulls(&self, count: u8) -> Result<Vec<PullRequest>, Box<dyn std::error::Error>>;
+
+    /// Fetch the Pull Request
+    async fn get_pull(&self, pr_id: u64) -> Result<PullRequest, Box<dyn std::error::Error>>;
+
+    /// Fetch the Commits in the Pull Request
+    async fn list_commits(&self, pr_id: u64) -> Result<Vec<Commit>, Box<dyn std::error::Error>>;
+
+    /// Fetch the Files changed by the Pull Request
+    async fn list_files(&self, pr_id: u64) -> Result<Vec<ChangedFile>, Box<dyn std::error::Error>>;
+
+    /// Fetch the Files changed by the Commit
+    async fn list_commit_files(&self, sha: &str) -> Result<Vec<ChangedFile>, Box<dyn std::error::Error>>;
+
+    /// Fetch the Reactions on the Pull Request
+    async fn list_reactions(&self, pr_id: u64) -> Result<Vec<Reaction>, Box<dyn std::error::Error>>;
+
+    /// Create a Reaction on the Pull Request
+    async fn create_reaction(&self, pr_id: u64, content: ReactionContent) -> Result<(), Box<dyn std::error::Error>>;
+
+    /// Delete a Reaction on the Pull Request
+    async fn delete_reaction(&self, pr_id: u64, reaction_id: u64) -> Result<(), Box<dyn std::error::Error>>;
+
+    /// Post a Comment on the Pull Request
+    async fn create_comment(&self, pr_id: u64, body: &str) -> Result<Comment, Box<dyn std::error::Error>>;
+
+    /// Add the Labels to the Pull Request
+    async fn add_labels(&self, pr_id: u64, labels: &[String]) -> Result<(), Box<dyn std::error::Error>>;
+
+    /// Remove the Label from the Pull Request
+    async fn remove_label(&self, pr_id: u64, label: &str) -> Result<(), Box<dyn std::error::Error>>;
+
+    /// Create a completed Check Run on the PR Head Commit
+    async fn create_check_run(&self, check: &CheckRun) -> Result<(), Box<dyn std::error::Error>>;
+
+    /// Create or update the Commit Status on the PR Head Commit
+    async fn create_status(&self, status: &CommitStatus) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+/// Forges that may be selected from the Command Line
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ForgeKind {
+    /// GitHub (needs `GITHUB_TOKEN`)
+    Github,
+
+    /// Gitea / Forgejo / Codeberg (needs `GITEA_TOKEN`)
+    Gitea,
+
+    /// GitLab Merge Requests (needs `GITLAB_TOKEN`)
+    Gitlab,
+
+    /// In-Memory Forge loaded from a JSON Fixture, for Testing (offline)
+    Memory,
+}
+
+impl ForgeKind {
+    /// Return true if the PRs are labelled with the PR Size (`Size: M`) by a Labeler, like on GitHub.
+    /// In-Memory Forge works like GitHub.
+    pub fn has_size_labels(&self) -> bool {
+        matches!(self, ForgeKind::Github | ForgeKind::Memory)
+    }
+}
+
+/// Command-Line Arguments for the Forge
+#[derive(clap::Args, Debug, Clone)]
+pub struct ForgeArgs {
+    /// Forge that hosts the PRs
+    #[arg(long, value_enum, default_value_t = ForgeKind::Github)]
+    pub forge: ForgeKind,
+
+    /// Base URL of the Forge Server (`https://codeberg.org` for Gitea, `https://gitlab.com` for GitLab)
+    #[arg(long)]
+    pub forge_url: Option<String>,
+
+    /// JSON Fixture with the PRs for the In-Memory Forge
+    #[arg(long)]
+    pub fixture: Option<String>,
+
+    /// Fetch the PRs and call the LLM, but don't post the PR Comments or change the PR Reactions.
+    /// PR Comments are written to stdout, or to `--output-dir`.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Directory for the PR Comments in Dry-Run Mode, saved as `<owner>-<repo>-<pr>.md`
+    #[arg(long, requires = "dry_run")]
+    pub output_dir: Option<String>,
+}
+
+/// Create the Forges for the Command-Line Arguments and Repos (Owner, Name).
+/// GitHub Repos will share the same GitHub Client. For Dry Run: Wrap the Forges with `DryRun`.
+pub fn new_forges(args: &ForgeArgs, repos: &[(String, String)]) -> Result<Vec<Box<dyn Forge>>, Box<dyn std::error::Error>> {
+    let octocrab = match args.forge {
+        ForgeKind::Github => Some(GitHub::client_from_env()?),
+        _ => None,
+    };
+    let mut forges: Vec<Box<dyn Forge>> = vec![];
+    for (owner, repo) in repos {
+        let forge: Box<dyn Forge> = match args.forge {
+            ForgeKind::Github => Box::new(GitHub::new(
+                octocrab.clone().unwrap(),
+                owner,
+                repo
+            )),
+            ForgeKind::Gitea => Box::new(Gitea::from_env(
+                args.forge_url.as_deref().unwrap_or(gitea::DEFAULT_URL),
+                owner,
+                repo
+            )?),
+            ForgeKind::Gitlab => Box::new(GitLab::from_env(
+                args.forge_url.as_deref().unwrap_or(gitlab::DEFAULT_URL),
+                owner,
+                repo
+            )?),
+            ForgeKind::Memory => Box::new(MemoryForge::from_fixture(
+                args.fixture.as_deref().ok_or("--fixture is required for the In-Memory Forge")?
+            )?),
+        };
+        let forge: Box<dyn Forge> = match args.dry_run {
+            true  => Box::new(DryRun::new(forge, args.output_dir.as_deref(), owner, repo)?),
+            false => forge,
+        };
+        forges.push(forge);
+    }
+    Ok(forges)
+}
@@ -0,0 +1,124 @@
+//! PR Labels for the LLM Verdict, like `needs: summary`, `needs: testing-logs` and `template: ok`.
+//!   Labels are added and removed on every review, so they stay in sync with the latest Verdict.
+//!   Only the Labels managed by the Bot are removed. Label Names are configurable per Repo.
+
+use log::info;
+use serde::Deserialize;
+use crate::{
+    forge::Forge,
+    verdict::{Outcome, Verdict},
+};
+
+/// Label Names for the Verdict. Empty Name means the Label is not used.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LabelConfig {
+    /// Apply the PR Labels for the Verdict
+    pub enabled: bool,
+
+    /// Label for the PR that meets the Requirements: Verdict passed and no Prechecks failed
+    pub ok: String,
+
+    /// Label for the PR that fails the Summary section
+    pub summary: String,
+
+    /// Label for the PR that fails the Impact section
+    pub impact: String,
+
+    /// Label for the PR that fails the Testing section, except the Testing Logs
+    pub testing: String,
+
+    /// Label for the PR that is missing the Testing Logs
+    pub testing_logs: String,
+}
+
+impl Default for LabelConfig {
+    /// Default Label Names. Labels are not applied unless enabled.
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ok: "template: ok".to_string(),
+            summary: "needs: summary".to_string(),
+            impact: "needs: impact".to_string(),
+            testing: "needs: testing".to_string(),
+            testing_logs: "needs: testing-logs".to_string(),
+        }
+    }
+}
+
+impl LabelConfig {
+    /// Return the Labels managed by the Bot
+    pub fn managed(&self) -> Vec<&String> {
+        [&self.ok, &self.summary, &self.impact, &self.testing, &self.testing_logs]
+            .into_iter()
+            .filter(|l| !l.is_empty())
+            .collect()
+    }
+}
+
+/// Return the Labels for the Verdict and the Prechecks
+pub fn verdict_labels(config: &LabelConfig, verdict: &Verdict, precheck: &str) -> Vec<String> {
+    // PR meets the Requirements: Verdict passed and no Prechecks failed
+    let mut labels: Vec<&String> = vec![];
+    if verdict.verdict == Outcome::Pass && precheck.is_empty() {
+        labels.push(&config.ok);
+    }
+
+    // Summary and Impact sections failed
+    let failed = |name: &str| verdict.sections
+        .iter()
+        .find(|s| s.name.eq_ignore_ascii_case(name) && !s.pass);
+    if failed("Summary").is_some() {
+        labels.push(&config.summary);
+    }
+    if failed("Impact").is_some() {
+        labels.push(&config.impact);
+    }
+
+    // Testing section failed: Missing Testing Logs, or missing something else
+    if let Some(testing) = failed("Testing") {
+        let (logs, others): (Vec<&String>, Vec<&String>) = testing.missing
+            .iter()
+            .partition(|item| item.to_lowercase().contains("log"));
+        if !logs.is_empty() {
+            labels.push(&config.testing_logs);
+        }
+        if !others.is_empty() || logs.is_empty() {
+            labels.push(&config.testing);
+        }
+    }
+    labels
+        .into_iter()
+        .filter(|l| !l.is_empty())
+        .cloned()
+        .collect()
+}
+
+/// Add and remove the PR Labels, so that the Managed Labels match the Verdict and the Prechecks.
+/// Current Labels are the PR Labels before the review.
+pub async fn sync_labels(forge: &dyn Forge, config: &LabelConfig, pr_id: u64, current: &[String], verdict: &Verdict, precheck: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if !config.enabled {
+        return Ok(());
+    }
+
+    // Add the Labels that are missing
+    let wanted = verdict_labels(config, verdict, precheck);
+    let add: Vec<String> = wanted
+        .iter()
+        .filter(|l| !current.contains(l))
+        .cloned()
+        .collect();
+    if !add.is_empty() {
+        info!("Add Labels: {:?}", add);
+        forge.add_labels(pr_id, &add).await?;
+    }
+
+    // Remove the Managed Labels that are no longer wanted
+    for label in config.managed() {
+        if current.contains(label) && !wanted.contains(label) {
+            info!("Remove Label: {:?}", label);
+            forge.remove_label(pr_id, label).await?;
+        }
+    }
+    Ok(())
+}
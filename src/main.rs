@@ -1,28 +1,41 @@
 //! Fetch the Latest 20 PRs:
 //!   If PR Status = Open
 //!   And PR Comments don't exist:
-//!     Then Call Gemini API to Validate the PR
-//!     And Post Gemini Response as PR Comment
-
-use std::{
-    env, 
-    thread::sleep, 
-    time::Duration
-};
-use clap::Parser;
-use log::info;
-use google_generative_ai_rs::v1::{
-    api::Client,
-    gemini::{request::Request, Content, Model, Part, Role},
-};
-use octocrab::{
-    issues::IssueHandler, 
-    models::{reactions::ReactionContent, IssueState, Label}, 
-    params,
-    pulls::PullRequestHandler
-};
-
-/// Requirements for PR Review
+//!     Then Call LLM (Gemini API) to Validate the PR
+//!     And Post LLM Response as PR Comment
+//! Or run as Daemon: Do the above for a list of Repos, forever
+//! Or run as Webhook Server: Do the above when a PR is opened or updated
+//! Or run as GitHub Actions Step: Do the above for the PR in the Workflow Event
+//! Repos, Rules and Prompts may be configured in a TOML Config File
+
+mod action;
+mod checks;
+mod commits;
+mod config;
+mod daemon;
+mod forge;
+mod labels;
+mod model;
+mod prompt;
+mod state;
+mod template;
+mod verdict;
+mod webhook;
+
+use std::time::Duration;
+use clap::{Parser, Subcommand};
+use log::{error, info};
+use tokio::time::sleep;
+use config::Config;
+use daemon::Repo;
+use config::Settings;
+use forge::{Comment, Forge, ForgeArgs, PullRequest, ReactionContent};
+use model::{ModelArgs, ReviewModel};
+use prompt::PromptVars;
+use state::StateStore;
+use verdict::Verdict;
+
+/// Default Requirements for PR Review
 const REQUIREMENTS: &str =
 r#####"
 # Here are the requirements for a NuttX PR
@@ -64,17 +77,106 @@ your testing logs here
 ```
 "#####;
 
+/// Default Header for PR Comment
+const HEADER: &str = "[**\\[Experimental Bot, please feedback here\\]**](https://github.com/search?q=repo%3Aapache%2Fnuttx+13552&type=issues)";
+
+/// Default Max Number of attempts to review a PR with the LLM
+const MAX_ATTEMPTS: u32 = 3;
+
 /// Command-Line Arguments
 #[derive(Parser, Debug)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, subcommand_negates_reqs = true)]
 struct Args {
-    /// Owner of the GitHub Repo that will be processed (`apache`)
-    #[arg(long)]
-    owner: String,
+    /// Owner of the GitHub Repo that will be processed (`apache`).
+    /// If omitted: Process the Repos in the Config File.
+    #[arg(long, requires = "repo")]
+    owner: Option<String>,
 
     /// Name of the GitHub Repo that will be processed (`nuttx` or `nuttx-apps`)
+    #[arg(long, requires = "owner")]
+    repo: Option<String>,
+
+    /// TOML Config File for the Repos, Rules and Prompts, like `nuttx-pr-bot.toml`
+    #[arg(long)]
+    config: Option<String>,
+
+    /// Forge that hosts the PRs
+    #[command(flatten)]
+    forge: ForgeArgs,
+
+    /// LLM Backend that will review the PRs
+    #[command(flatten)]
+    model: ModelArgs,
+
+    /// Don't call the LLM. Post only the Rule-Based Prechecks (PR Template and Commit Messages).
+    #[arg(long)]
+    no_llm: bool,
+
+    /// JSON State File that tracks the retries for each PR, instead of PR Reactions
     #[arg(long)]
-    repo: String,
+    state: Option<String>,
+
+    /// Subcommand. If omitted: Process the Repo once.
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Subcommands
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Process the PRs for a list of Repos forever, until SIGTERM or Ctrl-C
+    Daemon {
+        /// Repos that will be processed, like `apache/nuttx,apache/nuttx-apps`.
+        /// If omitted: Process the Repos in the Config File.
+        #[arg(long, value_delimiter = ',')]
+        repos: Vec<String>,
+
+        /// Seconds to wait after processing each Repo. If omitted: From the Config File, or 300.
+        #[arg(long)]
+        interval: Option<u64>,
+    },
+
+    /// Run a Webhook Server that reviews the PRs when they are opened or updated,
+    /// until SIGTERM or Ctrl-C (needs `GITHUB_WEBHOOK_SECRET`)
+    Serve {
+        /// Address and Port for the Webhook Server
+        #[arg(long, default_value = "0.0.0.0:3000")]
+        listen: String,
+
+        /// Repos that will be reviewed, like `apache/nuttx,apache/nuttx-apps`.
+        /// If omitted: The Repos in the Config File, or All Repos.
+        #[arg(long, value_delimiter = ',')]
+        repos: Vec<String>,
+    },
+
+    /// Run as a GitHub Actions Step: Review the PR in the Workflow Event
+    /// (needs `GITHUB_EVENT_PATH` and `GITHUB_REPOSITORY`)
+    Action,
+
+    /// Review the specific PRs in the Repo (`--owner` and `--repo`), on demand
+    Review {
+        /// PR Numbers that will be reviewed, like `13552,13553`
+        #[arg(long = "pr", value_delimiter = ',', required = true)]
+        prs: Vec<u64>,
+
+        /// Review the PRs even if they have PR Comments, or the PR Size is XS or Unknown
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// How the PR Review was triggered, which decides the PRs to be skipped
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Trigger {
+    /// Polling the Newest PRs: Skip the PRs with PR Comments, or PR Size XS or Unknown
+    Poll,
+
+    /// PR Event from the Webhook or GitHub Actions: Skip the PRs with PR Size XS,
+    /// or the PRs already reviewed with the same PR Body and Head Commit (needs the State Store)
+    Event,
+
+    /// Review on demand with `--force`: Never skip the Open PRs
+    Force,
 }
 
 /// Validate the Latest PRs and post the PR Reviews as PR Comments
@@ -84,220 +186,414 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::init();
     let args = Args::parse();
 
-    // Init the GitHub Client
-    let token = std::env::var("GITHUB_TOKEN")
-        .expect("GITHUB_TOKEN env variable is required");
-    let octocrab = octocrab::Octocrab::builder()
-        .personal_token(token)
-        .build()?;
+    // Load and validate the Config File, if specified
+    let mut config = match &args.config {
+        Some(path) => {
+            let config = Config::load(path)?;
+            info!("Config: {}", path);
+            config
+        }
+        None => Config::default(),
+    };
+
+    // Init the LLM Backend, unless we only run the Rule-Based Prechecks.
+    // Command-Line Args take priority over the Config File.
+    let model = if args.no_llm {
+        info!("LLM Backend: None (Prechecks only)");
+        None
+    } else {
+        let model = model::new_model(&config.model_args(&args.model))?;
+        info!("LLM Backend: {}", model.name());
+        Some(model)
+    };
+
+    // Dry Run shouldn't update the State Store, because the PR Comments are not posted
+    let state_path = if args.forge.dry_run { None } else { args.state.as_deref() };
+
+    // For GitHub Actions: Read the Repo and PR from the Workflow Event
+    let action_event = match &args.command {
+        Some(Command::Action) => Some(action::read_event()?),
+        _ => None,
+    };
+
+    // For GitHub Actions: PR Reactions are created by `github-actions[bot]`, unless the Config File sets the Bot Login
+    if action_event.is_some() && config.bot_login.is_none() {
+        config.bot_login = Some(action::BOT_LOGIN.to_string());
+    }
+
+    // Get the Repos to be processed: From the Daemon Subcommand, the Workflow Event, the Owner and Repo,
+    // or the Config File. For Webhook Server: Run forever.
+    let repos = match &args.command {
+        Some(Command::Daemon { repos, .. }) if repos.is_empty() => config.repos(),
+        Some(Command::Daemon { repos, .. }) => daemon::parse_repos(repos)?,
+        Some(Command::Serve { listen, repos }) => {
+            let allowed =
+                if repos.is_empty() { config.repos() }
+                else { daemon::parse_repos(repos)? };
+            webhook::run_webhook(listen, &allowed, &args.forge, model.as_deref(), state_path, &config).await?;
+            return Ok(());
+        }
+        Some(Command::Action) => {
+            let event = action_event.clone().unwrap();
+            vec![(event.owner, event.repo)]
+        }
+        Some(Command::Review { .. }) => vec![(
+            args.owner.clone().ok_or("--owner and --repo are required")?,
+            args.repo.clone().ok_or("--owner and --repo are required")?
+        )],
+        None => match (&args.owner, &args.repo) {
+            (Some(owner), Some(repo)) => vec![(owner.clone(), repo.clone())],
+            _ => config.repos(),
+        },
+    };
+    if repos.is_empty() {
+        return Err("Specify the Repos with --owner and --repo, --repos or the Config File".into());
+    }
+
+    // Init the Forge (GitHub), State Store (if specified) and Settings for each Repo
+    let forges = forge::new_forges(&args.forge, &repos)?;
+    let repos: Vec<Repo> = forges
+        .into_iter()
+        .zip(repos.iter())
+        .map(|(forge, (owner, repo))| {
+            info!("Forge: {}", forge.name());
+            Repo {
+                forge,
+                state: state_path.map(|path|
+                    StateStore::new(path, &format!("{}/{}", owner, repo))
+                ),
+                settings: config.settings(owner, repo, args.forge.forge),
+            }
+        })
+        .collect();
 
-    // Get the Handlers for GitHub Pull Requests and Issues
-    let pulls = octocrab.pulls(&args.owner, &args.repo);
-    let issues = octocrab.issues(&args.owner, &args.repo);
+    // Run the Daemon forever. Or process the PR in the Workflow Event. Or process the Repos once.
+    match &args.command {
+        Some(Command::Daemon { interval, .. }) => {
+            let interval = interval.unwrap_or(config.repo_interval());
+            daemon::run_daemon(&repos, model.as_deref(), Duration::from_secs(interval)).await?
+        }
+        Some(Command::Action) =>
+            process_pr(&repos[0], model.as_deref(), Trigger::Event, action_event.unwrap().pr_id).await?,
+        Some(Command::Review { prs, force }) =>
+            review_prs(&repos[0], model.as_deref(), *force, prs).await?,
+        Some(Command::Serve { .. }) | None =>
+            for repo in repos.iter() {
+                process_repo(repo, model.as_deref()).await?
+            },
+    }
+
+    // Return OK
+    Ok(())
+}
 
+/// Validate the Latest 20 PRs (or PR Count in Settings) in the Repo and post the PR Reviews as PR Comments
+async fn process_repo(repo: &Repo, model: Option<&dyn ReviewModel>) -> Result<(), Box<dyn std::error::Error>> {
     // Fetch the 20 Newest Pull Requests that are Open
-    let pr_list = pulls
-        .list()
-        .state(params::State::Open)
-        .sort(params::pulls::Sort::Created)
-        .direction(params::Direction::Descending)
-        .per_page(20)
-        .send()
+    let settings = &repo.settings;
+    let pr_list = repo.forge
+        .list_pulls(settings.pr_count)
         .await?;
 
-    // Every 5 Seconds: Process the next PR fetched
+    // Every 5 Seconds (PR Interval): Process the next PR fetched.
+    // Stop before the next PR if the Daemon is quitting.
     for pr in pr_list {
+        if daemon::is_shutdown() {
+            info!("Skipping the remaining PRs, Daemon is quitting");
+            break;
+        }
         let pr_id = pr.number;
-        process_pr(&pulls, &issues, pr_id)
+        process_pr(repo, model, Trigger::Poll, pr_id)
             .await?;
-        sleep(Duration::from_secs(5));
+        sleep(Duration::from_secs(settings.pr_interval)).await;
     }
 
     // Return OK
     Ok(())
 }
 
-/// Validate the PR by calling Gemini API. Then post the PR Review as a PR Comment
-async fn process_pr(pulls: &PullRequestHandler<'_>, issues: &IssueHandler<'_>, pr_id: u64) -> Result<(), Box<dyn std::error::Error>> {
+/// Validate the specific PRs and post the PR Reviews as PR Comments.
+/// If Force is true: Don't skip the PRs with PR Comments, or PR Size XS or Unknown.
+async fn review_prs(repo: &Repo, model: Option<&dyn ReviewModel>, force: bool, prs: &[u64]) -> Result<(), Box<dyn std::error::Error>> {
+    // Every 5 Seconds (PR Interval): Process the next PR
+    let trigger = if force { Trigger::Force } else { Trigger::Poll };
+    for (i, pr_id) in prs.iter().enumerate() {
+        if i > 0 {
+            sleep(Duration::from_secs(repo.settings.pr_interval)).await;
+        }
+        process_pr(repo, model, trigger, *pr_id)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Validate the PR by calling the LLM. Then post the PR Review as a PR Comment.
+/// If LLM Backend is None: Post only the Rule-Based Prechecks.
+/// If State Store is None: Track the retries with PR Reactions.
+/// Trigger decides the PRs to be skipped: Polling, PR Event or Force.
+async fn process_pr(repo: &Repo, model: Option<&dyn ReviewModel>, trigger: Trigger, pr_id: u64) -> Result<(), Box<dyn std::error::Error>> {
+    // Get the Forge, State Store and Settings for the Repo
+    let forge = repo.forge.as_ref();
+    let state = repo.state.as_ref();
+    let settings = &repo.settings;
+
     // Fetch the PR
-    let pr = pulls
-        .get(pr_id)
+    let pr = forge
+        .get_pull(pr_id)
         .await?;
     info!("{:#?}", pr.url);
 
     // Skip if PR State is Not Open
-    if pr.state.unwrap() != IssueState::Open {
+    if !pr.open {
         info!("Skipping Closed PR: {}", pr_id);
         return Ok(());
     }
 
-    // Skip if PR contains Comments
-    if pr.comments.unwrap() > 0 {
-        info!("Skipping PR with comments: {}", pr_id);
-        return Ok(());
+    // Skip if PR contains Comments. For PR Events: Skip if the same PR Body and Head Commit
+    // were already reviewed. Without the State Store, we can't tell if the PR has changed.
+    let body_hash = state::hash_body(&pr.body);
+    match (trigger, state) {
+        (Trigger::Poll, _) if pr.comments > 0 => {
+            info!("Skipping PR with comments: {}", pr_id);
+            return Ok(());
+        }
+        (Trigger::Event, Some(state)) if state.get(pr_id)?.is_reviewed(&body_hash, &pr.head_sha) => {
+            info!("Skipping PR already reviewed: {}", pr_id);
+            return Ok(());
+        }
+        (Trigger::Event, None) if pr.comments > 0 => {
+            info!("Skipping PR with comments, no State Store to detect the changes: {}", pr_id);
+            return Ok(());
+        }
+        _ => {}
     }
 
-    // Skip if PR Size is Unknown
-    let labels = pr.labels.unwrap();
-    if labels.is_empty() {
+    // Skip if PR Size is Unknown, unless the Forge doesn't apply the PR Size Labels.
+    // For PR Events: The PR Size Label may not be applied yet.
+    let labels = &pr.labels;
+    if labels.is_empty() && trigger == Trigger::Poll && settings.skip_unlabeled {
         info!("Skipping Unknown PR Size: {}", pr_id);
         return Ok(());
     }
 
-    // Skip if PR Size is XS
-    let size_xs: Vec<Label> = labels
-        .into_iter()
-        .filter(|l| l.name == "Size: XS")
+    // Skip if PR Size is XS (or another Skip Label in Settings)
+    let skip_labels: Vec<&String> = labels
+        .iter()
+        .filter(|l| settings.skip_labels.contains(l))
         .collect();
-    if size_xs.len() > 0 {
-        info!("Skipping PR Size XS: {}", pr_id);
+    if !skip_labels.is_empty() && trigger != Trigger::Force {
+        info!("Skipping PR with {:?}: {}", skip_labels, pr_id);
         return Ok(());
     }
 
-    // Fetch the PR Commits
-    // TODO: Change `pull_number` to `pr_commits`
-    let commits = pulls
-        .pull_number(pr_id)
-        .commits()
-        .await;
-    let commits = commits.unwrap().items;
+    // Fetch the PR Commits and the Files changed by the PR
+    let commits = forge
+        .list_commits(pr_id)
+        .await?;
+    let files = forge
+        .list_files(pr_id)
+        .await?;
+    let commit_files = commits::fetch_commit_files(forge, &settings.commits, &commits, &files).await?;
     let mut precheck = String::new();
 
-    // Check for Multiple Commits
-    // if commits.len() > 1 {
-    //     precheck.push_str(
-    //         &format!("__Squash The Commits:__ This PR contains {} Commits. Please Squash the Multiple Commits into a Single Commit.\n\n", commits.len())
-    //     );
-    // }
-
-    // Check for Empty Commit Message
-    let mut empty_message = false;
-    for commit in commits.iter() {
-        // Message should be "title\n\nbody"
-        let message = &commit.commit.message;
-        if message.find("\n").is_some() {
-        } else {
-            info!("Missing Commit Message: {:#?}", message);
-            empty_message = true;
-            break;
-        }
-    }
-    if empty_message {
-        precheck.push_str(
-            "__Fill In The Commit Message:__ This PR contains a Commit with an Empty Commit Message. Please fill in the Commit Message with the PR Summary.\n\n"
-        );
-    }
+    // Check for Multiple Commits, unless each Commit changes a different Subsystem or the PR has a Multi-Commit Label
+    precheck.push_str(
+        &commits::check_squash(&settings.commits, &commits, &commit_files, &pr.labels)
+    );
+
+    // Check the Commit Messages: Title Format and Length, Body, Signed-off-by, fixup! and Merge Commits.
+    // Check the Title Prefix against the Files changed by each Commit.
+    precheck.push_str(
+        &commits::check_commits(&settings.commits, &commits, &commit_files)
+    );
 
     // Get the PR Body
-    let body = pr.body.unwrap_or("".to_string());
+    let body = pr.body.clone();
     info!("PR Body: {:#?}", body);
 
-    // Retry Gemini API up to 3 times, by checking the PR Reactions.
-    // Fetch the PR Reactions. Quit if Both Reactions are set.
-    let reactions = get_reactions(issues, pr_id).await?;
-    if reactions.0.is_some() && reactions.1.is_some() {
-        info!("Skipping PR after 3 retries: {}", pr_id);
-        return Ok(());
-    }
+    // Check the PR Body for missing or unedited Summary, Impact and Testing
+    precheck.push_str(
+        &template::check_template(&body, &settings.requirements)
+    );
+    info!("Precheck: {:#?}", precheck);
+
+    // If there's no LLM: Post the Prechecks as PR Comment. Record the PR Comment in the State Store.
+    let model = match model {
+        Some(model) => model,
+        None => {
+            let comment = post_prechecks(forge, &settings.header, pr_id, &precheck).await?;
+            if let Some(state) = state {
+                state.update(pr_id, |s| {
+                    s.body_hash = Some(body_hash);
+                    s.head_sha = Some(pr.head_sha.clone());
+                    s.comment_id = Some(comment.id);
+                    s.comment_url = Some(comment.url.clone());
+                })?;
+            }
+            publish_results(forge, settings, &pr, &precheck, None, &comment.url).await;
+            return Ok(());
+        }
+    };
 
-    // Bump up the PR Reactions: 00 > 01 > 10 > 11
-    bump_reactions(issues, pr_id, reactions).await?;
+    // Retry the LLM up to 3 times (or Max Attempts in Settings), by checking the State Store or the PR Reactions.
+    // Dry Run won't read or bump the PR Reactions, since it never deletes them.
+    match state {
+        Some(state) => {
+            // Quit if we have tried 3 times. Otherwise bump up the attempts.
+            let pr_state = state.get(pr_id)?;
+            if pr_state.attempts >= settings.max_attempts {
+                info!("Skipping PR after {} retries: {} (last error: {:?})", settings.max_attempts, pr_id, pr_state.last_error);
+                return Ok(());
+            }
+            state.update(pr_id, |s| s.attempts += 1)?;
+        }
+        None if forge.is_dry_run() => {}
+        None => {
+            // Fetch the PR Reactions. Quit if Both Reactions are set.
+            let reactions = get_reactions(forge, &settings.bot_login, pr_id).await?;
+            if reactions.0.is_some() && reactions.1.is_some() {
+                info!("Skipping PR after 3 retries: {}", pr_id);
+                return Ok(());
+            }
 
-    // Init the Gemini Client
-    let client = Client::new_from_model(
-        Model::Gemini1_5Pro,  // For Production
-        // Model::GeminiPro,  // For Testing
-        env::var("GEMINI_API_KEY").unwrap().to_string()
-    );
+            // Bump up the PR Reactions: 00 > 01 > 10 > 11
+            bump_reactions(forge, pr_id, reactions).await?;
+        }
+    }
 
-    // Compose the Prompt for Gemini Request: PR Requirements + PR Body
-    let input = 
-        REQUIREMENTS.to_string() +
-        "\n\n# Does this PR meet the NuttX Requirements? Please be concise\n\n" +
-        &body;
+    // Compose the Prompt for LLM Request from the Prompt Templates: PR Requirements + PR Body.
+    // LLM shall return the Verdict as JSON.
+    let vars = PromptVars::new(&pr, &settings.requirements, &commits, &files, &precheck);
+    let mut prompt = prompt::render_prompt(&settings.system_prompt, &settings.user_prompt, &vars)?;
+    prompt.schema = Some(verdict::schema());
+    info!("Prompt: {:#?}", prompt.user);
 
     // For Testing:
     // let input = "# Here are the requirements for a NuttX PR\n\n## Summary\n\n* Why change is necessary (fix, update, new feature)?\n* What functional part of the code is being changed?\n* How does the change exactly work (what will change and how)?\n* Related [NuttX Issue](https://github.com/apache/nuttx/issues) reference if applicable.\n* Related NuttX Apps [Issue](https://github.com/apache/nuttx-apps/issues) / [Pull Request](https://github.com/apache/nuttx-apps/pulls) reference if applicable.\n\n## Impact\n\n* Is new feature added? Is existing feature changed?\n* Impact on user (will user need to adapt to change)? NO / YES (please describe if yes).\n* Impact on build (will build process change)? NO / YES (please descibe if yes).\n* Impact on hardware (will arch(s) / board(s) / driver(s) change)? NO / YES (please describe if yes).\n* Impact on documentation (is update required / provided)? NO / YES (please describe if yes).\n* Impact on security (any sort of implications)? NO / YES (please describe if yes).\n* Impact on compatibility (backward/forward/interoperability)? NO / YES (please describe if yes).\n* Anything else to consider?\n\n## Testing\n\nI confirm that changes are verified on local setup and works as intended:\n* Build Host(s): OS (Linux,BSD,macOS,Windows,..), CPU(Intel,AMD,ARM), compiler(GCC,CLANG,version), etc.\n* Target(s): arch(sim,RISC-V,ARM,..), board:config, etc.\n\nTesting logs before change:\n\n```\nyour testing logs here\n```\n\nTesting logs after change:\n```\nyour testing logs here\n```\n\n# Does this PR meet the NuttX Requirements?\n\n## Summary\nBCH: Add readonly configuration for BCH devices\n## Impact\nNONE\n## Testing\n";
 
-    // Compose the Gemini Request
-    let txt_request = Request {
-        contents: vec![Content {
-            role: Role::User,
-            parts: vec![Part {
-                text: Some(input.to_string()),
-                inline_data: None,
-                file_data: None,
-                video_metadata: None,
-            }],
-        }],
-        tools: vec![],
-        safety_settings: vec![],
-        generation_config: None,
-        system_instruction: None,
+    // Send the LLM Request and get the Verdict from the LLM Response.
+    // If it fails: Record the error in the State Store.
+    let result = match model.review(&prompt).await {
+        Ok(review) => {
+            info!("Response Text: {:#?}", review.text);
+            info!("Token Usage: {} prompt + {} response = {} total",
+                review.usage.prompt_tokens, review.usage.response_tokens, review.usage.total_tokens());
+            verdict::parse_verdict(&review.text)
+        }
+        Err(err) => Err(err),
     };
+    let verdict = match result {
+        Ok(verdict) => verdict,
+        Err(err) => {
+            if let Some(state) = state {
+                state.update(pr_id, |s| s.last_error = Some(err.to_string()))?;
+            }
+            return Err(err);
+        }
+    };
+    info!("Verdict: {:#?}", verdict);
 
-    // Send the Gemini Request
-    let response = client
-        .post(30, &txt_request)
-        .await?;
-    info!("Gemini Response: {:#?}", response);
-
-    // Get the Gemini Response
-    let response_text = 
-        response.rest().unwrap()
-        .candidates.first().unwrap()
-        .content.parts.first().unwrap()
-        .text.clone().unwrap();
-    info!("Response Text: {:#?}", response_text);
-
-    // Header for PR Comment
-    let header = "[**\\[Experimental Bot, please feedback here\\]**](https://github.com/search?q=repo%3Aapache%2Fnuttx+13552&type=issues)";
-
-    // Compose the PR Comment
+    // Compose the PR Comment from the Verdict
     let comment_text =
-        header.to_string() + "\n\n" +
+        settings.header.clone() + "\n\n" +
         &precheck + "\n\n" +
-        &response_text;
+        &verdict::render_verdict(&verdict, &precheck);
 
     // Post the PR Comment
-    let comment = issues
-        .create_comment(pr_id, comment_text)
+    let comment = forge
+        .create_comment(pr_id, &comment_text)
         .await?;
     info!("PR Comment: {:#?}", comment);       
 
-    // If successful, delete the PR Reactions
-    delete_reactions(issues, pr_id).await?;
+    // If successful, record the PR Comment in the State Store. Or delete the PR Reactions.
+    match state {
+        Some(state) => state.update(pr_id, |s| {
+            s.attempts = 0;
+            s.last_error = None;
+            s.body_hash = Some(body_hash);
+            s.head_sha = Some(pr.head_sha.clone());
+            s.comment_id = Some(comment.id);
+            s.comment_url = Some(comment.url.clone());
+            s.verdict = Some(verdict.clone());
+        })?,
+        None if forge.is_dry_run() => {}
+        None => delete_reactions(forge, &settings.bot_login, pr_id).await?,
+    }
+
+    // Add and remove the PR Labels for the Verdict, like `needs: summary`.
+    // Publish the Check Run and Commit Status.
+    publish_results(forge, settings, &pr, &precheck, Some(&verdict), &comment.url).await;
     info!("{:#?}", pr.url);
 
-    // Wait 1 minute
-    sleep(Duration::from_secs(60));
+    // Wait 1 minute (or Comment Interval in Settings). Dry Run posts nothing, so no need to wait.
+    if !forge.is_dry_run() {
+        sleep(Duration::from_secs(settings.comment_interval)).await;
+    }
 
     // Return OK
     Ok(())
 }
 
-/// Return the Reaction IDs for Rocket and Eyes Reactions, created by the Bot
-async fn get_reactions(issues: &IssueHandler<'_>, pr_id: u64) -> 
+/// After posting the PR Comment: Sync the PR Labels for the Verdict (if any), publish the Check Run
+/// and post the Commit Status. Errors are logged and skipped, because the PR Comment was already posted
+/// (e.g. the Bot may not have permission to update the PR Labels).
+async fn publish_results(forge: &dyn Forge, settings: &Settings, pr: &PullRequest, precheck: &str, verdict: Option<&Verdict>, comment_url: &str) {
+    // Add and remove the PR Labels for the Verdict, like `needs: summary`
+    if let Some(verdict) = verdict {
+        if let Err(err) = labels::sync_labels(forge, &settings.labels, pr.number, &pr.labels, verdict, precheck).await {
+            error!("Failed to sync PR Labels for PR {}: {}", pr.number, err);
+        }
+    }
+
+    // Publish the Check Run for the Prechecks and Verdict, next to the CI Checks
+    if let Err(err) = checks::publish_check_run(forge, settings, pr, precheck, verdict).await {
+        error!("Failed to publish Check Run for PR {}: {}", pr.number, err);
+    }
+
+    // Post the Commit Status for Branch Protection, linking to the PR Comment
+    if let Err(err) = checks::publish_status(forge, settings, pr, precheck, verdict, comment_url).await {
+        error!("Failed to post Commit Status for PR {}: {}", pr.number, err);
+    }
+}
+
+/// Post the Rule-Based Prechecks as PR Comment, without calling the LLM
+async fn post_prechecks(forge: &dyn Forge, header: &str, pr_id: u64, precheck: &str) ->
+    Result<Comment, Box<dyn std::error::Error>> {
+    // Compose the PR Comment
+    let precheck =
+        if precheck.is_empty() { "__PR Template:__ This PR contains the Summary, Impact and Testing sections. Thank you!" }
+        else { precheck };
+    let comment_text =
+        header.to_string() + "\n\n" +
+        precheck;
+
+    // Post the PR Comment
+    let comment = forge
+        .create_comment(pr_id, &comment_text)
+        .await?;
+    info!("PR Comment: {:#?}", comment);
+    Ok(comment)
+}
+
+/// Return the Reaction IDs for Rocket and Eyes Reactions, created by the Bot (`nuttxpr`)
+async fn get_reactions(forge: &dyn Forge, bot_login: &str, pr_id: u64) -> 
     Result<(Option<u64>, Option<u64>), Box<dyn std::error::Error>> {
     // Fetch the PR Reactions
-    let reactions = issues
+    let reactions = forge
         .list_reactions(pr_id)
-        .send()
         .await?;
-    let reactions = reactions.items;
 
     // Watch for Rocket and Eyes Reactions created by the Bot
-    // TODO: Change `nuttxpr` to the GitHub User ID of the Bot
     let mut result: (Option<u64>, Option<u64>) = (None, None);
     for reaction in reactions.iter() {
         let content = &reaction.content;
-        let user = &reaction.user.login;
-        let reaction_id = &reaction.id.0;
-        if user == "nuttxpr" {
+        let user = &reaction.user;
+        let reaction_id = &reaction.id;
+        if user == bot_login {
             match content {
-                ReactionContent::Rocket => { result.0 = Some(*reaction_id) }
-                ReactionContent::Eyes   => { result.1 = Some(*reaction_id) }
-                _ => {}
+                Some(ReactionContent::Rocket) => { result.0 = Some(*reaction_id) }
+                Some(ReactionContent::Eyes)   => { result.1 = Some(*reaction_id) }
+                None => {}
             }
         }
     }
@@ -305,44 +601,338 @@ async fn get_reactions(issues: &IssueHandler<'_>, pr_id: u64) ->
 }
 
 /// Bump up the 2 PR Reactions: 00 > 01 > 10 > 11
-/// Position 0 is the Rocket Reaction, Position 1 is the Eye Reaction
-async fn bump_reactions(issues: &IssueHandler<'_>, pr_id: u64, reactions: (Option<u64>, Option<u64>)) -> 
+/// Position 0 is the Rocket Reaction, Position 1 is the Eye Reaction. Fails if both are set.
+async fn bump_reactions(forge: &dyn Forge, pr_id: u64, reactions: (Option<u64>, Option<u64>)) -> 
     Result<(), Box<dyn std::error::Error>> {
     match reactions {
         // (Rocket, Eye)
-        (None,     None)    => { create_reaction(issues, pr_id, ReactionContent::Rocket).await?; }
-        (Some(id), None)    => { delete_reaction(issues, pr_id, id).await?; create_reaction(issues, pr_id, ReactionContent::Eyes).await?; }
-        (None,     Some(_)) => { create_reaction(issues, pr_id, ReactionContent::Rocket).await?; }
-        (Some(_),  Some(_)) => { panic!("Reaction Overflow") }
+        (None,     None)    => { forge.create_reaction(pr_id, ReactionContent::Rocket).await?; }
+        (Some(id), None)    => { forge.delete_reaction(pr_id, id).await?; forge.create_reaction(pr_id, ReactionContent::Eyes).await?; }
+        (None,     Some(_)) => { forge.create_reaction(pr_id, ReactionContent::Rocket).await?; }
+        (Some(_),  Some(_)) => { return Err(format!("Reaction Overflow: PR {} has both Reactions", pr_id).into()); }
     }
     Ok(())
 }
 
 /// Delete the PR Reactions
-async fn delete_reactions(issues: &IssueHandler<'_>, pr_id: u64) -> 
+async fn delete_reactions(forge: &dyn Forge, bot_login: &str, pr_id: u64) -> 
     Result<(), Box<dyn std::error::Error>> {
-    let reactions = get_reactions(issues, pr_id).await?;
+    let reactions = get_reactions(forge, bot_login, pr_id).await?;
     if let Some(reaction_id) = reactions.0 {
-        delete_reaction(issues, pr_id, reaction_id).await?;
+        forge.delete_reaction(pr_id, reaction_id).await?;
     }
     if let Some(reaction_id) = reactions.1 {
-        delete_reaction(issues, pr_id, reaction_id).await?;
+        forge.delete_reaction(pr_id, reaction_id).await?;
     }
     Ok(())
 }
 
-/// Create the PR Reaction
-async fn create_reaction(issues: &IssueHandler<'_>, pr_id: u64, content: ReactionContent) -> 
-    Result<(), Box<dyn std::error::Error>> {
-    issues.create_reaction(pr_id, content)
-        .await?;
-    Ok(())
-}
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, fs, rc::Rc};
+    use crate::forge::{ChangedFile, Commit, Fixture, MemoryForge, MemoryPull, PullRequest, Reaction, ReactionContent};
+    use crate::model::Mock;
+    use super::*;
 
-/// Delete the PR Reaction
-async fn delete_reaction(issues: &IssueHandler<'_>, pr_id: u64, reaction_id: u64) -> 
-    Result<(), Box<dyn std::error::Error>> {
-    issues.delete_reaction(pr_id, reaction_id)
-        .await?;
-    Ok(())
+    /// PR Body that passes the Prechecks and the Mock Review
+    const BODY: &str = r#"## Summary
+
+Fix the timer interrupt on rv-virt, which fired twice per tick.
+
+## Impact
+
+* Is new feature added? Is existing feature changed? NO
+* Impact on user (will user need to adapt to change)? NO
+* Impact on build (will build process change)? NO
+* Impact on hardware (will arch(s) / board(s) / driver(s) change)? YES, the rv-virt timer driver is fixed.
+* Impact on documentation (is update required / provided)? NO
+* Impact on security (any sort of implications)? NO
+* Impact on compatibility (backward/forward/interoperability)? NO
+* Anything else to consider? NO
+
+## Testing
+
+I confirm that changes are verified on local setup and works as intended:
+* Build Host(s): Ubuntu 24.04, x86_64, GCC 13
+* Target(s): rv-virt:nsh
+
+Testing logs before change:
+
+```
+nsh> sleep 1
+(took 2 seconds)
+```
+
+Testing logs after change:
+```
+nsh> sleep 1
+(took 1 second)
+```
+"#;
+
+    /// PR Number in the Fixture
+    const PR_ID: u64 = 7;
+
+    /// Create the Fixture with an Open PR that has the PR Body and one Commit
+    fn new_fixture(body: &str) -> Fixture {
+        Fixture {
+            pulls: vec![MemoryPull {
+                pull: PullRequest {
+                    number: PR_ID,
+                    open: true,
+                    labels: vec!["Size: S".to_string()],
+                    body: body.to_string(),
+                    head_sha: "1111111aaaa".to_string(),
+                    ..Default::default()
+                },
+                commits: vec![Commit {
+                    sha: "1111111aaaa".to_string(),
+                    message: "arch/risc-v: Fix the timer interrupt\n\nThe timer fired twice per tick.".to_string(),
+                }],
+                files: vec![ChangedFile {
+                    path: "arch/risc-v/src/qemu-rv/qemu_rv_timer.c".to_string(),
+                    additions: 3,
+                    deletions: 1,
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    /// Create the Repo with the In-Memory Forge. Return the Fixture, which will be updated by the Forge.
+    /// Labels are enabled and we don't wait after posting the PR Comment.
+    fn new_repo(fixture: Fixture, state_path: Option<&str>) -> (Repo, Rc<RefCell<Fixture>>) {
+        new_repo_with(MemoryForge::new(fixture), state_path)
+    }
+
+    /// Create the Repo with the In-Memory Forge. Return the Fixture, which will be updated by the Forge.
+    fn new_repo_with(forge: MemoryForge, state_path: Option<&str>) -> (Repo, Rc<RefCell<Fixture>>) {
+        let fixture = forge.fixture();
+        let mut settings = Settings {
+            comment_interval: 0,
+            pr_interval: 0,
+            ..Default::default()
+        };
+        settings.labels.enabled = true;
+        let repo = Repo {
+            forge: Box::new(forge),
+            state: state_path.map(|path| StateStore::new(path, "apache/nuttx")),
+            settings,
+        };
+        (repo, fixture)
+    }
+
+    /// Return the path of a new State File in the Temp Directory
+    fn state_path(name: &str) -> String {
+        let path = std::env::temp_dir()
+            .join(format!("nuttx-pr-bot-{}-{}.json", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path.to_string_lossy().to_string()
+    }
+
+    #[tokio::test]
+    async fn review_posts_comment_and_labels() {
+        let (repo, fixture) = new_repo(new_fixture(BODY), None);
+        let model = Mock::new(None).unwrap();
+        process_pr(&repo, Some(&model), Trigger::Poll, PR_ID).await.unwrap();
+
+        let fixture = fixture.borrow();
+        let pull = &fixture.pulls[0];
+        assert_eq!(pull.comments.len(), 1);
+        assert!(pull.comments[0].starts_with(HEADER));
+        assert!(pull.comments[0].contains("This PR meets the NuttX Requirements."));
+        assert!(pull.comments[0].contains("__Testing:__ OK"));
+        assert!(pull.reactions.is_empty());
+        assert!(pull.pull.labels.contains(&"template: ok".to_string()));
+    }
+
+    #[tokio::test]
+    async fn review_fails_missing_section() {
+        let body = BODY.replace("## Impact", "## Changes");
+        let (repo, fixture) = new_repo(new_fixture(&body), None);
+        let model = Mock::new(None).unwrap();
+        process_pr(&repo, Some(&model), Trigger::Poll, PR_ID).await.unwrap();
+
+        let fixture = fixture.borrow();
+        let pull = &fixture.pulls[0];
+        assert_eq!(pull.comments.len(), 1);
+        assert!(pull.comments[0].contains("__Impact:__ Please add the missing items:\n* `## Impact` section"));
+        assert!(!pull.comments[0].contains("This PR meets the NuttX Requirements."));
+        assert!(pull.pull.labels.contains(&"needs: impact".to_string()));
+        assert!(!pull.pull.labels.contains(&"template: ok".to_string()));
+    }
+
+    #[tokio::test]
+    async fn review_with_failed_prechecks() {
+        // Commit Message is empty, but the PR Body passes the Mock Review
+        let mut fixture = new_fixture(BODY);
+        fixture.pulls[0].commits[0].message = "arch/risc-v: Fix the timer interrupt".to_string();
+        fixture.pulls[0].pull.labels.push("template: ok".to_string());
+        let (repo, fixture) = new_repo(fixture, None);
+        let model = Mock::new(None).unwrap();
+        process_pr(&repo, Some(&model), Trigger::Poll, PR_ID).await.unwrap();
+
+        // PR doesn't meet the Requirements until the Prechecks are fixed: Remove the OK Label
+        let fixture = fixture.borrow();
+        let pull = &fixture.pulls[0];
+        assert_eq!(pull.comments.len(), 1);
+        assert!(pull.comments[0].contains("__Fix The Commit Messages:__"));
+        assert!(pull.comments[0].contains("The sections below look OK, but please fix the Prechecks above"));
+        assert!(!pull.comments[0].contains("This PR meets the NuttX Requirements."));
+        assert_eq!(pull.pull.labels, vec!["Size: S".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn review_skips_pr() {
+        // Skip the PR with Comments
+        let mut with_comments = new_fixture(BODY);
+        with_comments.pulls[0].pull.comments = 1;
+
+        // Skip the PR with Unknown Size
+        let mut unknown_size = new_fixture(BODY);
+        unknown_size.pulls[0].pull.labels.clear();
+
+        // Skip the PR with `Size: XS`
+        let mut size_xs = new_fixture(BODY);
+        size_xs.pulls[0].pull.labels = vec!["Size: XS".to_string()];
+
+        // Skip the Closed PR
+        let mut closed = new_fixture(BODY);
+        closed.pulls[0].pull.open = false;
+
+        let model = Mock::new(None).unwrap();
+        for fixture in [with_comments, unknown_size, size_xs, closed] {
+            let (repo, fixture) = new_repo(fixture, None);
+            process_pr(&repo, Some(&model), Trigger::Poll, PR_ID).await.unwrap();
+            let fixture = fixture.borrow();
+            assert!(fixture.pulls[0].comments.is_empty());
+            assert!(fixture.pulls[0].reactions.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn review_bumps_reactions_on_error() {
+        // Mock returns an invalid Verdict
+        let canned = std::env::temp_dir()
+            .join(format!("nuttx-pr-bot-canned-{}.txt", std::process::id()));
+        fs::write(&canned, "Not a JSON Verdict").unwrap();
+        let model = Mock::new(canned.to_str()).unwrap();
+        let (repo, fixture) = new_repo(new_fixture(BODY), None);
+
+        // Reactions are bumped for every attempt: 01 > 10 > 11
+        for count in [1, 1, 2] {
+            assert!(process_pr(&repo, Some(&model), Trigger::Poll, PR_ID).await.is_err());
+            assert_eq!(fixture.borrow().pulls[0].reactions.len(), count);
+        }
+
+        // After 3 attempts: Skip the PR. Reactions can't be bumped any more.
+        process_pr(&repo, Some(&model), Trigger::Poll, PR_ID).await.unwrap();
+        let reactions = get_reactions(repo.forge.as_ref(), &repo.settings.bot_login, PR_ID).await.unwrap();
+        assert!(bump_reactions(repo.forge.as_ref(), PR_ID, reactions).await.is_err());
+        let fixture = fixture.borrow();
+        assert_eq!(fixture.pulls[0].reactions.len(), 2);
+        assert!(fixture.pulls[0].comments.is_empty());
+        fs::remove_file(&canned).unwrap();
+    }
+
+    #[tokio::test]
+    async fn review_with_bot_login() {
+        // Mock returns an invalid Verdict
+        let canned = std::env::temp_dir()
+            .join(format!("nuttx-pr-bot-canned-login-{}.txt", std::process::id()));
+        fs::write(&canned, "Not a JSON Verdict").unwrap();
+        let model = Mock::new(canned.to_str()).unwrap();
+
+        // Reactions are created by `github-actions[bot]`, like GitHub Actions.
+        // Rocket Reaction by another User is not counted.
+        let mut fixture = new_fixture(BODY);
+        fixture.pulls[0].reactions.push(Reaction {
+            id: 100,
+            user: "nuttxpr".to_string(),
+            content: Some(ReactionContent::Rocket),
+        });
+        let forge = MemoryForge::new(fixture).with_login(action::BOT_LOGIN);
+        let (mut repo, fixture) = new_repo_with(forge, None);
+        repo.settings.bot_login = action::BOT_LOGIN.to_string();
+
+        // Reactions are bumped for every attempt: 01 > 10 > 11. After 3 attempts: Skip the PR.
+        for count in [2, 2, 3] {
+            assert!(process_pr(&repo, Some(&model), Trigger::Event, PR_ID).await.is_err());
+            assert_eq!(fixture.borrow().pulls[0].reactions.len(), count);
+        }
+        process_pr(&repo, Some(&model), Trigger::Event, PR_ID).await.unwrap();
+        assert_eq!(fixture.borrow().pulls[0].reactions.len(), 3);
+
+        // After the PR Comment is posted: Delete the Reactions by `github-actions[bot]`
+        fixture.borrow_mut().pulls[0].reactions.retain(|r| r.user != action::BOT_LOGIN);
+        fixture.borrow_mut().pulls[0].reactions.push(Reaction {
+            id: 101,
+            user: action::BOT_LOGIN.to_string(),
+            content: Some(ReactionContent::Rocket),
+        });
+        let model = Mock::new(None).unwrap();
+        process_pr(&repo, Some(&model), Trigger::Event, PR_ID).await.unwrap();
+        let fixture = fixture.borrow();
+        assert_eq!(fixture.pulls[0].comments.len(), 1);
+        assert_eq!(fixture.pulls[0].reactions.len(), 1);
+        assert_eq!(fixture.pulls[0].reactions[0].user, "nuttxpr");
+        fs::remove_file(&canned).unwrap();
+    }
+
+    #[tokio::test]
+    async fn review_event_with_state() {
+        let path = state_path("event");
+        let (repo, fixture) = new_repo(new_fixture(BODY), Some(&path));
+        let model = Mock::new(None).unwrap();
+
+        // Review the PR and record the PR Comment in the State Store. No Reactions.
+        process_pr(&repo, Some(&model), Trigger::Event, PR_ID).await.unwrap();
+        let pr_state = repo.state.as_ref().unwrap().get(PR_ID).unwrap();
+        assert_eq!(pr_state.attempts, 0);
+        assert_eq!(pr_state.body_hash, Some(state::hash_body(BODY)));
+        assert_eq!(pr_state.head_sha.as_deref(), Some("1111111aaaa"));
+        assert!(pr_state.comment_id.is_some());
+        assert_eq!(pr_state.verdict.unwrap().verdict, verdict::Outcome::Pass);
+        assert_eq!(fixture.borrow().pulls[0].comments.len(), 1);
+        assert!(fixture.borrow().pulls[0].reactions.is_empty());
+
+        // Skip the PR if nothing has changed
+        process_pr(&repo, Some(&model), Trigger::Event, PR_ID).await.unwrap();
+        assert_eq!(fixture.borrow().pulls[0].comments.len(), 1);
+
+        // Review again if the PR Body has changed
+        let body = BODY.replace("## Testing", "## Tests");
+        fixture.borrow_mut().pulls[0].pull.body = body.clone();
+        process_pr(&repo, Some(&model), Trigger::Event, PR_ID).await.unwrap();
+        assert_eq!(fixture.borrow().pulls[0].comments.len(), 2);
+        assert!(fixture.borrow().pulls[0].comments[1].contains("* `## Testing` section"));
+        assert!(fixture.borrow().pulls[0].pull.labels.contains(&"needs: testing".to_string()));
+        assert!(!fixture.borrow().pulls[0].pull.labels.contains(&"template: ok".to_string()));
+
+        // Review again if the PR Head Commit has changed
+        fixture.borrow_mut().pulls[0].pull.head_sha = "2222222bbbb".to_string();
+        process_pr(&repo, Some(&model), Trigger::Event, PR_ID).await.unwrap();
+        assert_eq!(fixture.borrow().pulls[0].comments.len(), 3);
+        let pr_state = repo.state.as_ref().unwrap().get(PR_ID).unwrap();
+        assert_eq!(pr_state.body_hash, Some(state::hash_body(&body)));
+        assert_eq!(pr_state.head_sha.as_deref(), Some("2222222bbbb"));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn review_without_llm() {
+        // Post the Prechecks for the Unedited PR Template
+        let (repo, fixture) = new_repo(new_fixture(REQUIREMENTS), None);
+        process_pr(&repo, None, Trigger::Poll, PR_ID).await.unwrap();
+
+        let fixture = fixture.borrow();
+        let pull = &fixture.pulls[0];
+        assert_eq!(pull.comments.len(), 1);
+        assert!(pull.comments[0].starts_with(HEADER));
+        assert!(pull.comments[0].contains("__Fill In The Summary Section:__"));
+        assert!(!pull.comments[0].contains("__NuttX Requirements:__"));
+        assert!(pull.reactions.is_empty());
+        assert_eq!(pull.pull.labels, vec!["Size: S".to_string()]);
+    }
 }
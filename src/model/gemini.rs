@@ -0,0 +1,112 @@
+//! Google Gemini API as LLM Backend
+
+use std::env;
+use async_trait::async_trait;
+use log::info;
+use google_generative_ai_rs::v1::{
+    api::Client,
+    gemini::{request::{GenerationConfig, Request}, Content, Model, Part, Role},
+};
+use serde_json::Value;
+use super::{Prompt, Review, ReviewModel, Usage};
+
+/// Timeout for the Gemini Request (seconds)
+const TIMEOUT: u64 = 30;
+
+/// Google Gemini API
+pub struct Gemini {
+    /// Gemini Client
+    client: Client,
+}
+
+impl Gemini {
+    /// Create the Gemini Client with the API Key from `GEMINI_API_KEY`
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let api_key = env::var("GEMINI_API_KEY")
+            .map_err(|_| "GEMINI_API_KEY env variable is required")?;
+        let client = Client::new_from_model(
+            Model::Gemini1_5Pro,  // For Production
+            // Model::GeminiPro,  // For Testing
+            api_key
+        );
+        Ok(Self { client })
+    }
+}
+
+#[async_trait(?Send)]
+impl ReviewModel for Gemini {
+    /// Name of the LLM Backend
+    fn name(&self) -> String {
+        "gemini".to_string()
+    }
+
+    /// Send the Prompt to Gemini and return the Review
+    async fn review(&self, prompt: &Prompt) -> Result<Review, Box<dyn std::error::Error>> {
+        // Compose the Gemini Request
+        let txt_request = Request {
+            contents: vec![Content {
+                role: Role::User,
+                parts: vec![Part {
+                    text: Some(prompt.to_text()),
+                    inline_data: None,
+                    file_data: None,
+                    video_metadata: None,
+                }],
+            }],
+            tools: vec![],
+            safety_settings: vec![],
+            generation_config: prompt.schema.as_ref().map(|schema| GenerationConfig {
+                temperature: None,
+                top_p: None,
+                top_k: None,
+                candidate_count: None,
+                max_output_tokens: None,
+                stop_sequences: None,
+                response_mime_type: Some("application/json".to_string()),
+                response_schema: Some(to_gemini_schema(schema)),
+            }),
+            system_instruction: None,
+        };
+
+        // Send the Gemini Request
+        let response = self.client
+            .post(TIMEOUT, &txt_request)
+            .await?;
+        info!("Gemini Response: {:#?}", response);
+
+        // Get the Gemini Response
+        let response = response.rest()
+            .ok_or("Gemini returned a Streamed Response")?;
+        let text = response
+            .candidates.first().ok_or("Gemini returned no Candidates")?
+            .content.parts.first().ok_or("Gemini returned no Parts")?
+            .text.clone().ok_or("Gemini returned no Text")?;
+
+        // Get the Token Usage
+        let usage = match response.usage_metadata {
+            Some(metadata) => Usage {
+                prompt_tokens: metadata.prompt_token_count,
+                response_tokens: metadata.candidates_token_count,
+            },
+            None => Usage::default(),
+        };
+        Ok(Review { text, usage })
+    }
+}
+
+/// Convert the JSON Schema to Gemini Schema, which has Uppercase Types (`OBJECT`, `STRING`, ...)
+fn to_gemini_schema(schema: &Value) -> Value {
+    match schema {
+        Value::Object(map) => Value::Object(
+            map.iter().map(|(key, value)| {
+                let value = match (key.as_str(), value) {
+                    ("type", Value::String(t)) => Value::String(t.to_uppercase()),
+                    _ => to_gemini_schema(value),
+                };
+                (key.clone(), value)
+            }).collect()
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(to_gemini_schema).collect()),
+        _ => schema.clone(),
+    }
+}
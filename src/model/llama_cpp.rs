@@ -0,0 +1,97 @@
+//! Local llama.cpp Server as LLM Backend, for reviewing PRs offline.
+//! See https://github.com/ggerganov/llama.cpp/blob/master/examples/server/README.md
+
+use std::time::Duration;
+use async_trait::async_trait;
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use super::{Prompt, Review, ReviewModel, Usage};
+
+/// Default Base URL of the llama.cpp Server
+pub const DEFAULT_URL: &str = "http://localhost:8080";
+
+/// Timeout for the llama.cpp Request (seconds). Local Models are slower than Gemini.
+const TIMEOUT: u64 = 300;
+
+/// Max Number of Tokens to be predicted
+const MAX_TOKENS: i32 = 2048;
+
+/// Local llama.cpp Server. The Model is selected when starting the server.
+pub struct LlamaCpp {
+    /// HTTP Client
+    client: reqwest::Client,
+
+    /// Base URL of the llama.cpp Server, like `http://localhost:8080`
+    url: String,
+}
+
+/// Request for llama.cpp Completion API
+#[derive(Serialize)]
+struct CompletionRequest<'a> {
+    prompt: String,
+    n_predict: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    json_schema: Option<&'a Value>,
+}
+
+/// Response from llama.cpp Completion API
+#[derive(Deserialize)]
+struct CompletionResponse {
+    content: String,
+    #[serde(default)]
+    tokens_evaluated: u64,
+    #[serde(default)]
+    tokens_predicted: u64,
+}
+
+impl LlamaCpp {
+    /// Create the llama.cpp Client for the Base URL
+    pub fn new(url: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(TIMEOUT))
+            .build()?;
+        Ok(Self {
+            client,
+            url: url.trim_end_matches('/').to_string(),
+        })
+    }
+}
+
+#[async_trait(?Send)]
+impl ReviewModel for LlamaCpp {
+    /// Name of the LLM Backend
+    fn name(&self) -> String {
+        format!("llama.cpp (at {})", self.url)
+    }
+
+    /// Send the Prompt to llama.cpp and return the Review
+    async fn review(&self, prompt: &Prompt) -> Result<Review, Box<dyn std::error::Error>> {
+        // Compose the llama.cpp Request: PR Requirements + PR Body, JSON Schema as Grammar
+        let request = CompletionRequest {
+            prompt: prompt.to_text(),
+            n_predict: MAX_TOKENS,
+            json_schema: prompt.schema.as_ref(),
+        };
+
+        // Send the llama.cpp Request
+        let response: CompletionResponse = self.client
+            .post(format!("{}/completion", self.url))
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        info!("llama.cpp Response: {:#?}", response.content);
+
+        // Return the Review and Token Usage
+        Ok(Review {
+            text: response.content,
+            usage: Usage {
+                prompt_tokens: response.tokens_evaluated,
+                response_tokens: response.tokens_predicted,
+            },
+        })
+    }
+}
@@ -0,0 +1,149 @@
+//! Mock LLM Backend for Testing and Dry Runs, without network.
+//! Returns a Canned Review (from a file) or a JSON Verdict derived from the PR Template Sections.
+
+use std::fs;
+use async_trait::async_trait;
+use crate::{
+    template::{find_section, parse_sections, SECTIONS},
+    verdict::{Outcome, SectionVerdict, Verdict},
+};
+use super::{Prompt, Review, ReviewModel, Usage};
+
+/// Mock LLM Backend
+pub struct Mock {
+    /// Canned Review (JSON Verdict) that will be returned for every PR.
+    /// If None: Verdict is derived from the PR Template Sections.
+    canned: Option<String>,
+}
+
+impl Mock {
+    /// Create the Mock Backend. Load the Canned Review from the file, if specified.
+    pub fn new(canned_path: Option<&str>) -> Result<Self, Box<dyn std::error::Error>> {
+        let canned = match canned_path {
+            Some(path) => Some(
+                fs::read_to_string(path)
+                    .map_err(|e| format!("Unable to read Mock Response {}: {}", path, e))?
+            ),
+            None => None,
+        };
+        Ok(Self { canned })
+    }
+}
+
+#[async_trait(?Send)]
+impl ReviewModel for Mock {
+    /// Name of the LLM Backend
+    fn name(&self) -> String {
+        match self.canned {
+            Some(_) => "mock (canned)".to_string(),
+            None    => "mock (rules)".to_string(),
+        }
+    }
+
+    /// Return the Canned Review, or derive the Verdict from the PR Template Sections
+    async fn review(&self, prompt: &Prompt) -> Result<Review, Box<dyn std::error::Error>> {
+        let text = match &self.canned {
+            Some(canned) => canned.clone(),
+            None => serde_json::to_string_pretty(&review_sections(&prompt.user))?,
+        };
+
+        // Count the words as tokens, so that the usage is deterministic
+        let usage = Usage {
+            prompt_tokens: prompt.to_text().split_whitespace().count() as u64,
+            response_tokens: text.split_whitespace().count() as u64,
+        };
+        Ok(Review { text, usage })
+    }
+}
+
+/// Review the PR Body by checking for the `## Summary`, `## Impact` and `## Testing` headers
+fn review_sections(input: &str) -> Verdict {
+    // Pass the Sections that exist, fail the Sections that are missing
+    let sections = parse_sections(input);
+    let sections: Vec<SectionVerdict> = SECTIONS
+        .iter()
+        .map(|name| {
+            let pass = find_section(&sections, name).is_some();
+            SectionVerdict {
+                name: name.to_string(),
+                pass,
+                missing:
+                    if pass { vec![] }
+                    else { vec![format!("`## {}` section", name)] },
+            }
+        })
+        .collect();
+
+    // Compose the Verdict
+    let verdict =
+        if sections.iter().all(|s| s.pass) { Outcome::Pass }
+        else { Outcome::Fail };
+    Verdict {
+        sections,
+        verdict,
+        confidence: 1.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        forge::PullRequest,
+        prompt::{render_prompt, PromptVars, SYSTEM_TEMPLATE, USER_TEMPLATE},
+        verdict::{parse_verdict, render_verdict},
+    };
+    use super::*;
+
+    /// Return the Sections that failed
+    fn failed(verdict: &Verdict) -> Vec<&str> {
+        verdict.sections
+            .iter()
+            .filter(|s| !s.pass)
+            .map(|s| s.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn review_missing_sections() {
+        // All Sections exist
+        let verdict = review_sections("## Summary\nFix\n## Impact\nNONE\n## Testing\nOK\n");
+        assert_eq!(verdict.verdict, Outcome::Pass);
+        assert!(failed(&verdict).is_empty());
+
+        // Impact and Testing are missing
+        let verdict = review_sections("## Summary\nFix\n");
+        assert_eq!(verdict.verdict, Outcome::Fail);
+        assert_eq!(failed(&verdict), vec!["Impact", "Testing"]);
+        assert_eq!(verdict.sections[1].missing, vec!["`## Impact` section"]);
+
+        // Headers inside Code Blocks don't count
+        let verdict = review_sections("## Summary\nFix\n## Impact\nNONE\n```\n## Testing\n```\n");
+        assert_eq!(failed(&verdict), vec!["Testing"]);
+
+        // Empty PR Body
+        let verdict = review_sections("");
+        assert_eq!(failed(&verdict), vec!["Summary", "Impact", "Testing"]);
+    }
+
+    #[tokio::test]
+    async fn review_prompt() {
+        // Render the Prompt for the PR without the Testing Section.
+        // The Requirements in the System Prompt contain all Sections, but they are not reviewed.
+        let pr = PullRequest {
+            body: "## Summary\nFix the timer\n## Impact\nNONE\n".to_string(),
+            ..Default::default()
+        };
+        let vars = PromptVars::new(&pr, crate::REQUIREMENTS, &[], &[], "");
+        let prompt = render_prompt(SYSTEM_TEMPLATE, USER_TEMPLATE, &vars).unwrap();
+
+        // Mock returns the JSON Verdict, which is parsed and rendered as PR Comment
+        let review = Mock::new(None).unwrap().review(&prompt).await.unwrap();
+        assert!(review.usage.prompt_tokens > 0);
+        let verdict = parse_verdict(&review.text).unwrap();
+        assert_eq!(verdict.verdict, Outcome::Fail);
+        assert_eq!(failed(&verdict), vec!["Testing"]);
+        let comment = render_verdict(&verdict, "");
+        assert!(comment.contains("__Summary:__ OK"));
+        assert!(comment.contains("__Testing:__ Please add the missing items:\n* `## Testing` section\n"));
+    }
+}
@@ -0,0 +1,140 @@
+//! LLM Backends that will review the PR:
+//!   Prompt goes in (PR Requirements + PR Body + JSON Schema of the Verdict)
+//!   Review Text (JSON Verdict) and Token Usage come out
+
+mod gemini;
+mod llama_cpp;
+mod mock;
+mod ollama;
+mod openai;
+
+use async_trait::async_trait;
+use clap::ValueEnum;
+use serde::Deserialize;
+pub use gemini::Gemini;
+pub use llama_cpp::LlamaCpp;
+pub use mock::Mock;
+pub use ollama::Ollama;
+pub use openai::OpenAi;
+
+/// Prompt for the LLM: System Instructions (PR Requirements) + User Input (PR Body)
+#[derive(Debug, Clone)]
+pub struct Prompt {
+    /// Instructions for the LLM, like the PR Requirements
+    pub system: String,
+
+    /// User Input for the LLM, like the question and PR Body
+    pub user: String,
+
+    /// JSON Schema for the Response. If set: LLM should return JSON that matches the Schema.
+    pub schema: Option<serde_json::Value>,
+}
+
+impl Prompt {
+    /// Return the System Instructions and User Input as a single text,
+    /// for LLMs that don't support System Instructions
+    pub fn to_text(&self) -> String {
+        self.system.clone() + "\n\n" + &self.user
+    }
+}
+
+/// Review returned by the LLM
+#[derive(Debug, Clone)]
+pub struct Review {
+    /// Review Text that will be posted as PR Comment
+    pub text: String,
+
+    /// Tokens consumed by the LLM
+    pub usage: Usage,
+}
+
+/// Token Usage reported by the LLM
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Usage {
+    /// Number of Tokens in the Prompt
+    pub prompt_tokens: u64,
+
+    /// Number of Tokens in the Response
+    pub response_tokens: u64,
+}
+
+impl Usage {
+    /// Total Tokens consumed by the Prompt and Response
+    pub fn total_tokens(&self) -> u64 {
+        self.prompt_tokens + self.response_tokens
+    }
+}
+
+/// LLM Backend that reviews the PR
+#[async_trait(?Send)]
+pub trait ReviewModel {
+    /// Name of the LLM Backend, for logging
+    fn name(&self) -> String;
+
+    /// Send the Prompt to the LLM and return the Review
+    async fn review(&self, prompt: &Prompt) -> Result<Review, Box<dyn std::error::Error>>;
+}
+
+/// LLM Backends that may be selected from the Command Line
+#[derive(ValueEnum, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ModelKind {
+    /// Google Gemini API (needs `GEMINI_API_KEY`)
+    Gemini,
+
+    /// Local Ollama Server (offline)
+    Ollama,
+
+    /// Local llama.cpp Server (offline)
+    LlamaCpp,
+
+    /// OpenAI-compatible Chat Completions API: OpenAI, vLLM, LiteLLM, ... (`OPENAI_API_KEY` if needed)
+    Openai,
+
+    /// Mock Backend for Testing and Dry Runs (offline, deterministic)
+    Mock,
+}
+
+/// Command-Line Arguments for the LLM Backend
+#[derive(clap::Args, Debug, Clone)]
+pub struct ModelArgs {
+    /// LLM Backend that will review the PRs. If omitted: From the Config File, or `gemini`.
+    #[arg(long, value_enum)]
+    pub model: Option<ModelKind>,
+
+    /// Base URL of the LLM Server (`http://localhost:11434` for Ollama, `http://localhost:8080` for llama.cpp,
+    /// `https://api.openai.com/v1` for OpenAI)
+    #[arg(long)]
+    pub llm_url: Option<String>,
+
+    /// Name of the Model served by the LLM Server (`llama3.1` for Ollama, `gpt-4o-mini` for OpenAI)
+    #[arg(long)]
+    pub llm_model: Option<String>,
+
+    /// File containing the Canned Review (JSON Verdict) for the Mock Backend.
+    /// If omitted: Mock Backend derives the Verdict from the PR Template Sections.
+    #[arg(long)]
+    pub mock_response: Option<String>,
+}
+
+/// Create the LLM Backend for the Command-Line Arguments
+pub fn new_model(args: &ModelArgs) -> Result<Box<dyn ReviewModel>, Box<dyn std::error::Error>> {
+    let model: Box<dyn ReviewModel> = match args.model.unwrap_or(ModelKind::Gemini) {
+        ModelKind::Gemini => Box::new(Gemini::from_env()?),
+        ModelKind::Ollama => Box::new(Ollama::new(
+            args.llm_url.as_deref().unwrap_or(ollama::DEFAULT_URL),
+            args.llm_model.as_deref().unwrap_or(ollama::DEFAULT_MODEL)
+        )?),
+        ModelKind::LlamaCpp => Box::new(LlamaCpp::new(
+            args.llm_url.as_deref().unwrap_or(llama_cpp::DEFAULT_URL)
+        )?),
+        ModelKind::Openai => Box::new(OpenAi::new(
+            args.llm_url.as_deref().unwrap_or(openai::DEFAULT_URL),
+            args.llm_model.as_deref().unwrap_or(openai::DEFAULT_MODEL)
+        )?),
+        ModelKind::Mock => Box::new(Mock::new(
+            args.mock_response.as_deref()
+        )?),
+    };
+    Ok(model)
+}
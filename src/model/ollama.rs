@@ -0,0 +1,114 @@
+//! Local Ollama Server as LLM Backend, for reviewing PRs offline.
+//! See https://github.com/ollama/ollama/blob/main/docs/api.md#generate-a-chat-completion
+
+use std::time::Duration;
+use async_trait::async_trait;
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use super::{Prompt, Review, ReviewModel, Usage};
+
+/// Default Base URL of the Ollama Server
+pub const DEFAULT_URL: &str = "http://localhost:11434";
+
+/// Default Model served by Ollama
+pub const DEFAULT_MODEL: &str = "llama3.1";
+
+/// Timeout for the Ollama Request (seconds). Local Models are slower than Gemini.
+const TIMEOUT: u64 = 300;
+
+/// Local Ollama Server
+pub struct Ollama {
+    /// HTTP Client
+    client: reqwest::Client,
+
+    /// Base URL of the Ollama Server, like `http://localhost:11434`
+    url: String,
+
+    /// Name of the Model, like `llama3.1`
+    model: String,
+}
+
+/// Request for Ollama Chat API
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage>,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<&'a Value>,
+}
+
+/// Chat Message for Ollama Chat API
+#[derive(Serialize, Deserialize)]
+struct ChatMessage {
+    role: String,
+    content: String,
+}
+
+/// Response from Ollama Chat API
+#[derive(Deserialize)]
+struct ChatResponse {
+    message: ChatMessage,
+    #[serde(default)]
+    prompt_eval_count: u64,
+    #[serde(default)]
+    eval_count: u64,
+}
+
+impl Ollama {
+    /// Create the Ollama Client for the Base URL and Model
+    pub fn new(url: &str, model: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(TIMEOUT))
+            .build()?;
+        Ok(Self {
+            client,
+            url: url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+        })
+    }
+}
+
+#[async_trait(?Send)]
+impl ReviewModel for Ollama {
+    /// Name of the LLM Backend
+    fn name(&self) -> String {
+        format!("ollama ({} at {})", self.model, self.url)
+    }
+
+    /// Send the Prompt to Ollama and return the Review
+    async fn review(&self, prompt: &Prompt) -> Result<Review, Box<dyn std::error::Error>> {
+        // Compose the Ollama Request: PR Requirements as System Message, PR Body as User Message,
+        // JSON Schema as Structured Output
+        let request = ChatRequest {
+            model: &self.model,
+            messages: vec![
+                ChatMessage { role: "system".to_string(), content: prompt.system.clone() },
+                ChatMessage { role: "user".to_string(),   content: prompt.user.clone() },
+            ],
+            stream: false,
+            format: prompt.schema.as_ref(),
+        };
+
+        // Send the Ollama Request
+        let response: ChatResponse = self.client
+            .post(format!("{}/api/chat", self.url))
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        info!("Ollama Response: {:#?}", response.message.content);
+
+        // Return the Review and Token Usage
+        Ok(Review {
+            text: response.message.content,
+            usage: Usage {
+                prompt_tokens: response.prompt_eval_count,
+                response_tokens: response.eval_count,
+            },
+        })
+    }
+}
@@ -0,0 +1,141 @@
+//! OpenAI-compatible Chat Completions API as LLM Backend.
+//! Works with OpenAI, vLLM, LiteLLM and other gateways that speak `/v1/chat/completions`.
+//! See https://platform.openai.com/docs/api-reference/chat/create
+
+use std::{env, time::Duration};
+use async_trait::async_trait;
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use super::{Prompt, Review, ReviewModel, Usage};
+
+/// Default Base URL of the OpenAI API. Includes the `/v1` path, like the OpenAI SDKs.
+pub const DEFAULT_URL: &str = "https://api.openai.com/v1";
+
+/// Default Model for the OpenAI API
+pub const DEFAULT_MODEL: &str = "gpt-4o-mini";
+
+/// Timeout for the Chat Completions Request (seconds)
+const TIMEOUT: u64 = 120;
+
+/// OpenAI-compatible Chat Completions API
+pub struct OpenAi {
+    /// HTTP Client
+    client: reqwest::Client,
+
+    /// Base URL of the API, like `https://api.openai.com/v1`
+    url: String,
+
+    /// Name of the Model, like `gpt-4o-mini`
+    model: String,
+
+    /// API Key from `OPENAI_API_KEY`. Optional for Local Gateways.
+    api_key: Option<String>,
+}
+
+/// Request for Chat Completions API
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<Value>,
+}
+
+/// Chat Message for Chat Completions API
+#[derive(Serialize, Deserialize)]
+struct ChatMessage {
+    role: String,
+    content: String,
+}
+
+/// Response from Chat Completions API
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+    usage: Option<ChatUsage>,
+}
+
+/// Choice returned by Chat Completions API
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatMessage,
+}
+
+/// Token Usage returned by Chat Completions API
+#[derive(Deserialize)]
+struct ChatUsage {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+}
+
+impl OpenAi {
+    /// Create the Chat Completions Client for the Base URL and Model,
+    /// with the API Key from `OPENAI_API_KEY` (if set)
+    pub fn new(url: &str, model: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(TIMEOUT))
+            .build()?;
+        Ok(Self {
+            client,
+            url: url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+            api_key: env::var("OPENAI_API_KEY").ok(),
+        })
+    }
+}
+
+#[async_trait(?Send)]
+impl ReviewModel for OpenAi {
+    /// Name of the LLM Backend
+    fn name(&self) -> String {
+        format!("openai ({} at {})", self.model, self.url)
+    }
+
+    /// Send the Prompt to the Chat Completions API and return the Review
+    async fn review(&self, prompt: &Prompt) -> Result<Review, Box<dyn std::error::Error>> {
+        // Compose the Chat Request: PR Requirements as System Message, PR Body as User Message,
+        // JSON Schema as Structured Output
+        let request = ChatRequest {
+            model: &self.model,
+            messages: vec![
+                ChatMessage { role: "system".to_string(), content: prompt.system.clone() },
+                ChatMessage { role: "user".to_string(),   content: prompt.user.clone() },
+            ],
+            response_format: prompt.schema.as_ref().map(|schema| json!({
+                "type": "json_schema",
+                "json_schema": { "name": "verdict", "schema": schema }
+            })),
+        };
+
+        // Send the Chat Request, with the API Key if set
+        let mut request = self.client
+            .post(format!("{}/chat/completions", self.url))
+            .json(&request);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        let response: ChatResponse = request
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        // Get the Chat Response
+        let text = response.choices
+            .into_iter().next().ok_or("Chat Completions returned no Choices")?
+            .message.content;
+        info!("OpenAI Response: {:#?}", text);
+
+        // Get the Token Usage
+        let usage = match response.usage {
+            Some(usage) => Usage {
+                prompt_tokens: usage.prompt_tokens,
+                response_tokens: usage.completion_tokens,
+            },
+            None => Usage::default(),
+        };
+        Ok(Review { text, usage })
+    }
+}
@@ -0,0 +1,191 @@
+//! Prompt Templates for the LLM, rendered with minijinja:
+//!   System Template becomes the System Instructions, User Template becomes the User Input.
+//!   Templates are loaded from files (see `prompts/`), so the Prompt may be changed without recompiling.
+//!
+//! Variables for the Templates:
+//!   `requirements`, `title`, `body`, `author`, `labels`, `precheck`,
+//!   `commits` (`sha`, `message`), `files` (`path`, `additions`, `deletions`),
+//!   `files_summary` (Compact Summary of the Changed Files)
+
+use minijinja::{Environment, UndefinedBehavior};
+use serde::Serialize;
+use crate::{
+    commits::subsystems,
+    forge::{ChangedFile, Commit, PullRequest},
+    model::Prompt,
+};
+
+/// Max Number of Changed Files listed in the Files Summary
+const MAX_SUMMARY_FILES: usize = 30;
+
+/// Max Number of Subsystems listed in the Files Summary
+const MAX_SUMMARY_SUBSYSTEMS: usize = 5;
+
+/// Default System Template: The PR Requirements
+pub const SYSTEM_TEMPLATE: &str = include_str!("../prompts/system.md");
+
+/// Default User Template: The question and PR Body
+pub const USER_TEMPLATE: &str = include_str!("../prompts/user.md");
+
+/// Variables for the Prompt Templates
+#[derive(Debug, Serialize)]
+pub struct PromptVars<'a> {
+    /// Requirements for PR Review
+    pub requirements: &'a str,
+
+    /// PR Title
+    pub title: &'a str,
+
+    /// PR Body
+    pub body: &'a str,
+
+    /// Login of the PR Author
+    pub author: &'a str,
+
+    /// Names of the PR Labels
+    pub labels: &'a [String],
+
+    /// Commits in the PR
+    pub commits: &'a [Commit],
+
+    /// Files changed by the PR
+    pub files: &'a [ChangedFile],
+
+    /// Compact Summary of the Files changed by the PR, so the LLM may check the Impact
+    pub files_summary: String,
+
+    /// Results of the Rule-Based Prechecks (Markdown), empty if all passed
+    pub precheck: &'a str,
+}
+
+impl<'a> PromptVars<'a> {
+    /// Collect the Variables for the PR
+    pub fn new(pr: &'a PullRequest, requirements: &'a str, commits: &'a [Commit], files: &'a [ChangedFile], precheck: &'a str) -> Self {
+        Self {
+            requirements,
+            title: &pr.title,
+            body: &pr.body,
+            author: &pr.author,
+            labels: &pr.labels,
+            commits,
+            files,
+            files_summary: files_summary(files),
+            precheck,
+        }
+    }
+}
+
+/// Summarise the Changed Files: Totals, Subsystems and the Files with Additions / Deletions.
+/// Looks like `3 file(s) changed (+120 -4) in arch/risc-v, boards/risc-v` followed by `* path (+3 -1)`
+pub fn files_summary(files: &[ChangedFile]) -> String {
+    if files.is_empty() {
+        return "No files changed".to_string();
+    }
+
+    // Totals and Subsystems
+    let additions: u64 = files.iter().map(|f| f.additions).sum();
+    let deletions: u64 = files.iter().map(|f| f.deletions).sum();
+    let subsystems = subsystems(files);
+    let mut summary = format!("{} file(s) changed (+{} -{}) in {}",
+        files.len(), additions, deletions,
+        subsystems.iter().take(MAX_SUMMARY_SUBSYSTEMS).cloned().collect::<Vec<String>>().join(", "));
+    if subsystems.len() > MAX_SUMMARY_SUBSYSTEMS {
+        summary.push_str(&format!(" and {} more", subsystems.len() - MAX_SUMMARY_SUBSYSTEMS));
+    }
+    summary.push('\n');
+
+    // Files with Additions and Deletions
+    for file in files.iter().take(MAX_SUMMARY_FILES) {
+        summary.push_str(&format!("* {} (+{} -{})\n", file.path, file.additions, file.deletions));
+    }
+    if files.len() > MAX_SUMMARY_FILES {
+        summary.push_str(&format!("* ... and {} more files\n", files.len() - MAX_SUMMARY_FILES));
+    }
+    summary
+}
+
+/// Render the System and User Templates into the Prompt
+pub fn render_prompt(system_template: &str, user_template: &str, vars: &PromptVars) -> Result<Prompt, Box<dyn std::error::Error>> {
+    Ok(Prompt {
+        system: render(system_template, vars)?,
+        user: render(user_template, vars)?,
+        schema: None,
+    })
+}
+
+/// Check the Template for Syntax Errors and Unknown Variables, by rendering it for a Sample PR
+pub fn check_template(template: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let pr = PullRequest {
+        title: "arch/sim: Sample PR".to_string(),
+        labels: vec!["Size: S".to_string()],
+        ..Default::default()
+    };
+    let commits = [Commit::default()];
+    let files = [ChangedFile::default()];
+    let vars = PromptVars::new(&pr, "", &commits, &files, "");
+    render(template, &vars)?;
+    Ok(())
+}
+
+/// Render the Template with the Variables. Unknown Variables will fail.
+fn render(template: &str, vars: &PromptVars) -> Result<String, Box<dyn std::error::Error>> {
+    let mut env = Environment::new();
+    env.set_undefined_behavior(UndefinedBehavior::Strict);
+    let text = env.render_str(template, vars)?;
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Create the Changed File
+    fn file(path: &str, additions: u64, deletions: u64) -> ChangedFile {
+        ChangedFile { path: path.to_string(), additions, deletions }
+    }
+
+    #[test]
+    fn summary() {
+        assert_eq!(files_summary(&[]), "No files changed");
+
+        // Subsystems with the most files come first
+        let files = [
+            file("boards/risc-v/qemu-rv/rv-virt/configs/nsh/defconfig", 1, 0),
+            file("arch/risc-v/src/qemu-rv/qemu_rv_timer.c", 100, 4),
+            file("arch/risc-v/Kconfig", 19, 0),
+        ];
+        assert_eq!(files_summary(&files), concat!(
+            "3 file(s) changed (+120 -4) in arch/risc-v, boards/risc-v\n",
+            "* boards/risc-v/qemu-rv/rv-virt/configs/nsh/defconfig (+1 -0)\n",
+            "* arch/risc-v/src/qemu-rv/qemu_rv_timer.c (+100 -4)\n",
+            "* arch/risc-v/Kconfig (+19 -0)\n",
+        ));
+    }
+
+    #[test]
+    fn summary_truncated() {
+        // 35 Files in 7 Subsystems: Totals count every File
+        let files: Vec<ChangedFile> = (0..35)
+            .map(|i| file(&format!("drivers/d{}/file{}.c", i % 7, i), 2, 1))
+            .collect();
+        let summary = files_summary(&files);
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(lines[0], "35 file(s) changed (+70 -35) in drivers/d0, drivers/d1, drivers/d2, drivers/d3, drivers/d4 and 2 more");
+        assert_eq!(lines.len(), 1 + MAX_SUMMARY_FILES + 1);
+        assert_eq!(lines[1], "* drivers/d0/file0.c (+2 -1)");
+        assert_eq!(lines[MAX_SUMMARY_FILES], "* drivers/d1/file29.c (+2 -1)");
+        assert_eq!(lines[MAX_SUMMARY_FILES + 1], "* ... and 5 more files");
+        assert!(!summary.contains("file30.c"));
+    }
+
+    #[test]
+    fn render_summary() {
+        // Files Summary is rendered in the Default User Template
+        let pr = PullRequest { body: "## Summary\nFix".to_string(), ..Default::default() };
+        let files = [file("arch/risc-v/Kconfig", 19, 0)];
+        let vars = PromptVars::new(&pr, "", &[], &files, "");
+        let prompt = render_prompt(SYSTEM_TEMPLATE, USER_TEMPLATE, &vars).unwrap();
+        assert!(prompt.user.contains("## Summary\nFix"));
+        assert!(prompt.user.contains("1 file(s) changed (+19 -0) in arch/risc-v\n* arch/risc-v/Kconfig (+19 -0)\n"));
+    }
+}
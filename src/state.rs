@@ -0,0 +1,117 @@
+//! Persistent State Store for the PRs, saved as a JSON File.
+//! Tracks the retries without writing visible Reactions on the PRs:
+//!   Number of attempts, last error, hash of the reviewed PR Body and Head Commit, the posted PR Comment and the LLM Verdict
+
+use std::{collections::BTreeMap, fs, path::Path};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::verdict::Verdict;
+
+/// State of a PR
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrState {
+    /// Number of attempts to review the PR, reset after the PR Comment is posted
+    pub attempts: u32,
+
+    /// Last error while reviewing the PR
+    pub last_error: Option<String>,
+
+    /// SHA-256 Hash of the PR Body that was reviewed
+    pub body_hash: Option<String>,
+
+    /// SHA of the PR Head Commit that was reviewed
+    pub head_sha: Option<String>,
+
+    /// ID of the PR Comment that was posted
+    pub comment_id: Option<u64>,
+
+    /// Web URL of the PR Comment that was posted
+    pub comment_url: Option<String>,
+
+    /// Verdict returned by the LLM, for Statistics
+    pub verdict: Option<Verdict>,
+}
+
+impl PrState {
+    /// Return true if the PR Comment was posted for the same PR Body and Head Commit
+    pub fn is_reviewed(&self, body_hash: &str, head_sha: &str) -> bool {
+        self.comment_id.is_some() &&
+            self.body_hash.as_deref() == Some(body_hash) &&
+            self.head_sha.as_deref() == Some(head_sha)
+    }
+}
+
+/// Contents of the State File: Repo (`apache/nuttx`) > PR Number > PR State
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StateFile {
+    repos: BTreeMap<String, BTreeMap<u64, PrState>>,
+}
+
+/// State Store for the PRs in a Repo.
+/// The State File is reloaded for every update, so it may be shared by multiple Repos.
+pub struct StateStore {
+    /// Path of the JSON State File
+    path: String,
+
+    /// Repo that owns the PRs, like `apache/nuttx`
+    repo: String,
+}
+
+impl StateStore {
+    /// Create the State Store for the Repo, saved in the JSON State File
+    pub fn new(path: &str, repo: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            repo: repo.to_string(),
+        }
+    }
+
+    /// Return the State of the PR. Default State if the PR is not found.
+    pub fn get(&self, pr_id: u64) -> Result<PrState, Box<dyn std::error::Error>> {
+        let state = self.load()?;
+        let pr_state = state.repos
+            .get(&self.repo)
+            .and_then(|prs| prs.get(&pr_id))
+            .cloned()
+            .unwrap_or_default();
+        Ok(pr_state)
+    }
+
+    /// Update the State of the PR and save the State File
+    pub fn update(&self, pr_id: u64, f: impl FnOnce(&mut PrState)) -> Result<(), Box<dyn std::error::Error>> {
+        let mut state = self.load()?;
+        let pr_state = state.repos
+            .entry(self.repo.clone())
+            .or_default()
+            .entry(pr_id)
+            .or_default();
+        f(pr_state);
+        self.save(&state)
+    }
+
+    /// Load the State File. Empty State if the file doesn't exist.
+    fn load(&self) -> Result<StateFile, Box<dyn std::error::Error>> {
+        if !Path::new(&self.path).exists() {
+            return Ok(StateFile::default());
+        }
+        let json = fs::read_to_string(&self.path)?;
+        let state = serde_json::from_str(&json)
+            .map_err(|e| format!("Invalid State File {}: {}", self.path, e))?;
+        Ok(state)
+    }
+
+    /// Save the State File. Write to a Temp File then rename, so the State File is never truncated.
+    fn save(&self, state: &StateFile) -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_string_pretty(state)?;
+        let tmp_path = format!("{}.tmp", self.path);
+        fs::write(&tmp_path, json)?;
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Return the SHA-256 Hash of the PR Body, in hex
+pub fn hash_body(body: &str) -> String {
+    format!("{:x}", Sha256::digest(body.as_bytes()))
+}
@@ -0,0 +1,448 @@
+//! Rule-based Parser for the NuttX PR Template:
+//!   Split the PR Body into the `## Summary`, `## Impact` and `## Testing` sections,
+//!   extract the YES / NO answer for each Impact bullet,
+//!   find the unedited placeholders in the Testing section,
+//!   and report the missing or unedited sections
+
+/// Sections required by the NuttX PR Template
+pub const SECTIONS: [&str; 3] = ["Summary", "Impact", "Testing"];
+
+/// Placeholder for the Testing Logs in the NuttX PR Template
+const LOG_PLACEHOLDER: &str = "your testing logs here";
+
+/// Fields in the Testing section that should be filled in
+const TESTING_FIELDS: [&str; 2] = ["Build Host(s)", "Target(s)"];
+
+/// Section of the PR Body, like `## Summary`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    /// Section Name, like `Summary`
+    pub name: String,
+
+    /// Lines below the Section Header, until the next Section Header
+    pub content: String,
+}
+
+/// Code Block in the PR Body, like the Testing Logs
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeBlock {
+    /// Last line of text before the Code Block, like `Testing logs before change:`
+    pub caption: String,
+
+    /// Lines inside the Code Block
+    pub content: String,
+}
+
+/// Answer to an Impact bullet, like `Impact on user ...? NO`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    /// Answered YES, with the description that follows
+    Yes(String),
+
+    /// Answered NO
+    No,
+
+    /// Still says `NO / YES`, or no answer given
+    Unanswered,
+}
+
+/// Impact bullet, like `Impact on hardware (...)? NO`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Impact {
+    /// Impact Topic, like `hardware`
+    pub topic: String,
+
+    /// YES / NO Answer
+    pub answer: Answer,
+}
+
+/// Split the text into Sections by the Markdown Headers (`#`, `##`, `###`, ...).
+/// Text before the first Header is skipped. Headers inside Code Blocks are ignored.
+pub fn parse_sections(text: &str) -> Vec<Section> {
+    let mut sections: Vec<Section> = vec![];
+    let mut in_code = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        }
+        let header = if in_code { None } else { header_name(line) };
+        match header {
+            Some(name) => sections.push(Section {
+                name,
+                content: String::new(),
+            }),
+            None => if let Some(section) = sections.last_mut() {
+                section.content.push_str(line);
+                section.content.push('\n');
+            },
+        }
+    }
+    sections
+}
+
+/// Find the Section by name (case-insensitive)
+pub fn find_section<'a>(sections: &'a [Section], name: &str) -> Option<&'a Section> {
+    sections
+        .iter()
+        .find(|s| s.name.eq_ignore_ascii_case(name))
+}
+
+/// Extract the YES / NO Answers from the `Impact on ...` bullets
+pub fn parse_impacts(content: &str) -> Vec<Impact> {
+    let mut impacts = vec![];
+    for line in content.lines() {
+        // Bullet should be "* Impact on user (will user need to adapt to change)? NO"
+        let line = line.trim();
+        let bullet = match line.strip_prefix('*').or(line.strip_prefix('-')) {
+            Some(bullet) => bullet.trim(),
+            None => continue,
+        };
+        let topic = match bullet.strip_prefix("Impact on ") {
+            Some(rest) => rest
+                .split(|c: char| !c.is_alphanumeric())
+                .next()
+                .unwrap_or("")
+                .to_lowercase(),
+            None => continue,
+        };
+
+        // Answer comes after the question. Or after the colon, like "Impact on user: NO"
+        let answer_text = match bullet.find(")?") {
+            Some(pos) => &bullet[pos + 2..],
+            None => match bullet.find('?').or(bullet.find(':')) {
+                Some(pos) => &bullet[pos + 1..],
+                None => "",
+            },
+        };
+        impacts.push(Impact { topic, answer: parse_answer(answer_text) });
+    }
+    impacts
+}
+
+/// Check the PR Body against the PR Requirements.
+/// Return the Precheck Paragraphs for the missing or unedited sections and Impact answers.
+pub fn check_template(body: &str, requirements: &str) -> String {
+    let mut precheck = String::new();
+    let sections = parse_sections(body);
+    let template = parse_sections(requirements);
+    for name in SECTIONS {
+        // Section must exist
+        let section = match find_section(&sections, name) {
+            Some(section) => section,
+            None => {
+                precheck.push_str(
+                    &format!("__Add The {name} Section:__ This PR is missing the `## {name}` section. Please fill in the `## {name}` section from the NuttX PR Template.\n\n")
+                );
+                continue;
+            }
+        };
+
+        // Section must not be empty or copied from the PR Template
+        let template_lines: Vec<&str> = find_section(&template, name)
+            .map(|s| s.content.lines().map(|l| l.trim()).collect())
+            .unwrap_or_default();
+        let edited = section.content
+            .lines()
+            .map(|l| l.trim())
+            .any(|l| !l.is_empty() && !template_lines.contains(&l));
+        if !edited {
+            precheck.push_str(
+                &format!("__Fill In The {name} Section:__ The `## {name}` section is empty or unchanged from the NuttX PR Template. Please fill in the `## {name}` section.\n\n")
+            );
+            continue;
+        }
+
+        // Testing section must not contain unedited placeholders
+        if name == "Testing" {
+            precheck.push_str(
+                &check_testing(&section.content, &template_lines)
+            );
+        }
+    }
+
+    // Impact bullets must be answered YES or NO. YES must be described.
+    if let Some(impact) = find_section(&sections, "Impact") {
+        for item in parse_impacts(&impact.content) {
+            let topic = &item.topic;
+            match item.answer {
+                Answer::Unanswered => precheck.push_str(
+                    &format!("__Answer The Impact Question:__ Please answer NO or YES for `Impact on {topic}`.\n\n")
+                ),
+                Answer::Yes(description) if description.is_empty() => precheck.push_str(
+                    &format!("__Describe The Impact:__ `Impact on {topic}` is YES. Please describe the impact.\n\n")
+                ),
+                _ => {}
+            }
+        }
+    }
+    precheck
+}
+
+/// Check the Testing section for unedited placeholders:
+/// Empty `Build Host(s)` and `Target(s)`, `your testing logs here`, empty logs, identical logs before and after
+pub fn check_testing(content: &str, template_lines: &[&str]) -> String {
+    let mut precheck = String::new();
+
+    // Build Host(s) and Target(s) must be filled in, and not copied from the PR Template
+    for field in TESTING_FIELDS {
+        let template_value = field_value(template_lines.iter().copied(), field);
+        match field_value(content.lines(), field) {
+            Some(value) if !value.is_empty() && Some(value) != template_value => {}
+            Some(_) => precheck.push_str(
+                &format!("__Fill In The {field}:__ `{field}` in the Testing section is empty or unchanged from the NuttX PR Template. Please fill in `{field}`.\n\n")
+            ),
+            None => {}
+        }
+    }
+
+    // Testing Logs must not contain the placeholder
+    let blocks = parse_code_blocks(content);
+    if blocks.iter().any(|b| b.content.trim().eq_ignore_ascii_case(LOG_PLACEHOLDER)) {
+        precheck.push_str(
+            &format!("__Fill In The Testing Logs:__ The Testing section still says `{LOG_PLACEHOLDER}`. Please replace it with the actual Testing Logs.\n\n")
+        );
+    }
+
+    // Testing Logs must not be empty
+    if blocks.iter().any(|b| b.content.trim().is_empty()) {
+        precheck.push_str(
+            "__Fill In The Testing Logs:__ The Testing section contains an empty block of Testing Logs. Please paste the actual Testing Logs.\n\n"
+        );
+    }
+
+    // Testing Logs before and after the change must be different
+    let before = blocks.iter().find(|b| b.caption.to_lowercase().contains("before"));
+    let after = blocks.iter().find(|b| b.caption.to_lowercase().contains("after"));
+    if let (Some(before), Some(after)) = (before, after) {
+        let log = before.content.trim();
+        if !log.is_empty() && !log.eq_ignore_ascii_case(LOG_PLACEHOLDER) && log == after.content.trim() {
+            precheck.push_str(
+                "__Check The Testing Logs:__ Testing Logs before and after the change are identical. Please show how the change affects the Testing Logs.\n\n"
+            );
+        }
+    }
+    precheck
+}
+
+/// Extract the Code Blocks (between ```) and the line of text before each Code Block
+pub fn parse_code_blocks(content: &str) -> Vec<CodeBlock> {
+    let mut blocks = vec![];
+    let mut caption = String::new();
+    let mut block: Option<String> = None;
+    for line in content.lines() {
+        let is_fence = line.trim_start().starts_with("```");
+        match (&mut block, is_fence) {
+            // Start of Code Block
+            (None, true) => block = Some(String::new()),
+            // Text outside Code Block: Remember the Caption
+            (None, false) => if !line.trim().is_empty() { caption = line.trim().to_string(); },
+            // End of Code Block
+            (Some(text), true) => {
+                blocks.push(CodeBlock { caption: caption.clone(), content: text.clone() });
+                block = None;
+            }
+            // Text inside Code Block
+            (Some(text), false) => { text.push_str(line); text.push('\n'); }
+        }
+    }
+    blocks
+}
+
+/// Return the value of the bullet, like `Build Host(s): Ubuntu 24.04` returns `Ubuntu 24.04`
+fn field_value<'a>(lines: impl Iterator<Item = &'a str>, field: &str) -> Option<&'a str> {
+    lines
+        .map(|line| line.trim().trim_start_matches(['*', '-']).trim())
+        .find_map(|line| line.strip_prefix(field))
+        .map(|value| value.trim_start_matches(':').trim())
+}
+
+/// Return the Section Name if the line is a Markdown Header, like `## Summary`
+fn header_name(line: &str) -> Option<String> {
+    let name = line.trim().strip_prefix('#')?;
+    let name = name.trim_start_matches('#');
+
+    // Header must be followed by a space: `#include` is not a Header
+    if !name.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let name = name
+        .trim()
+        .trim_end_matches(':')
+        .trim();
+    Some(name.to_string())
+}
+
+/// Parse the Answer: "NO", "YES, this changes ...", "NO / YES (please describe if yes)."
+fn parse_answer(text: &str) -> Answer {
+    let text = text.trim();
+    if text.to_uppercase().starts_with("NO / YES") {
+        return Answer::Unanswered;
+    }
+    let word: String = text
+        .chars()
+        .take_while(|c| c.is_alphabetic())
+        .collect();
+    match word.to_uppercase().as_str() {
+        "NO" => Answer::No,
+        "YES" => {
+            let description = text[word.len()..]
+                .trim_start_matches(|c: char| c.is_whitespace() || c.is_ascii_punctuation())
+                .trim();
+            if description.to_lowercase().starts_with("please describe if yes") {
+                Answer::Yes(String::new())
+            } else {
+                Answer::Yes(description.to_string())
+            }
+        }
+        _ => Answer::Unanswered,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Testing section of the NuttX PR Template
+    fn template_testing() -> String {
+        let template = parse_sections(crate::REQUIREMENTS);
+        find_section(&template, "Testing").unwrap().content.clone()
+    }
+
+    #[test]
+    fn sections() {
+        let sections = parse_sections("Intro\n## Summary\nFix\n### Impact:\nNONE\n#include <stdio.h>\n# Testing\nOK\n");
+        let names: Vec<&str> = sections.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Summary", "Impact", "Testing"]);
+        assert_eq!(sections[1].content, "NONE\n#include <stdio.h>\n");
+        assert_eq!(find_section(&sections, "testing").unwrap().content, "OK\n");
+        assert!(find_section(&sections, "Changes").is_none());
+    }
+
+    #[test]
+    fn sections_in_code_blocks() {
+        // Headers inside Code Blocks are part of the Section
+        let sections = parse_sections("## Testing\n```\n## Impact\n# comment\n```\n## Summary\nFix\n");
+        let names: Vec<&str> = sections.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Testing", "Summary"]);
+        assert_eq!(sections[0].content, "```\n## Impact\n# comment\n```\n");
+    }
+
+    #[test]
+    fn answers() {
+        let cases = [
+            ("NO", Answer::No),
+            (" no.", Answer::No),
+            ("NO / YES (please describe if yes).", Answer::Unanswered),
+            ("", Answer::Unanswered),
+            ("Maybe", Answer::Unanswered),
+            ("YES", Answer::Yes(String::new())),
+            ("YES (please describe if yes).", Answer::Yes(String::new())),
+            ("YES, the timer driver is fixed.", Answer::Yes("the timer driver is fixed.".to_string())),
+            ("yes: new Kconfig option", Answer::Yes("new Kconfig option".to_string())),
+        ];
+        for (text, answer) in cases {
+            assert_eq!(parse_answer(text), answer, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn impacts() {
+        let impacts = parse_impacts(concat!(
+            "* Is new feature added? Is existing feature changed? NO\n",
+            "* Impact on user (will user need to adapt to change)? NO\n",
+            "* Impact on build (will build process change)? NO / YES (please descibe if yes).\n",
+            "- Impact on hardware (will arch(s) / board(s) / driver(s) change)? YES\n",
+            "* Impact on documentation: YES, updated the docs\n",
+            "Impact on security? NO\n",
+        ));
+        assert_eq!(impacts, vec![
+            Impact { topic: "user".to_string(), answer: Answer::No },
+            Impact { topic: "build".to_string(), answer: Answer::Unanswered },
+            Impact { topic: "hardware".to_string(), answer: Answer::Yes(String::new()) },
+            Impact { topic: "documentation".to_string(), answer: Answer::Yes("updated the docs".to_string()) },
+        ]);
+    }
+
+    #[test]
+    fn unedited_template() {
+        // Every Section is unchanged from the PR Template
+        let precheck = check_template(crate::REQUIREMENTS, crate::REQUIREMENTS);
+        for name in SECTIONS {
+            assert!(precheck.contains(&format!("__Fill In The {name} Section:__")), "{name}");
+        }
+        assert_eq!(precheck.matches("__Answer The Impact Question:__").count(), 6);
+        assert!(!precheck.contains("__Fill In The Testing Logs:__"));
+    }
+
+    #[test]
+    fn missing_sections() {
+        let precheck = check_template("## Summary\nFix the timer\n", crate::REQUIREMENTS);
+        assert!(!precheck.contains("`## Summary`"));
+        assert!(precheck.contains("__Add The Impact Section:__"));
+        assert!(precheck.contains("__Add The Testing Section:__"));
+        assert!(check_template("", crate::REQUIREMENTS).contains("__Add The Summary Section:__"));
+    }
+
+    #[test]
+    fn unanswered_impacts() {
+        let body = concat!(
+            "## Summary\nFix the timer\n",
+            "## Impact\n",
+            "* Impact on user (will user need to adapt to change)? NO / YES (please describe if yes).\n",
+            "* Impact on build (will build process change)? YES\n",
+            "* Impact on hardware (will arch(s) / board(s) / driver(s) change)? YES, rv-virt timer\n",
+            "## Testing\n* Build Host(s): Ubuntu\n* Target(s): rv-virt:nsh\n",
+        );
+        let precheck = check_template(body, crate::REQUIREMENTS);
+        assert_eq!(precheck, concat!(
+            "__Answer The Impact Question:__ Please answer NO or YES for `Impact on user`.\n\n",
+            "__Describe The Impact:__ `Impact on build` is YES. Please describe the impact.\n\n",
+        ));
+    }
+
+    #[test]
+    fn testing_fields() {
+        let template = template_testing();
+        let lines: Vec<&str> = template.lines().map(|l| l.trim()).collect();
+        assert_eq!(check_testing("* Build Host(s): Ubuntu\n* Target(s): rv-virt:nsh\n", &lines), "");
+
+        // Fields copied from the PR Template, or empty
+        let content = "* Build Host(s): OS (Linux,BSD,macOS,Windows,..), CPU(Intel,AMD,ARM), compiler(GCC,CLANG,version), etc.\n* Target(s):\n";
+        let precheck = check_testing(content, &lines);
+        assert!(precheck.contains("__Fill In The Build Host(s):__"));
+        assert!(precheck.contains("__Fill In The Target(s):__"));
+    }
+
+    #[test]
+    fn testing_logs() {
+        let template = template_testing();
+        let lines: Vec<&str> = template.lines().map(|l| l.trim()).collect();
+        let cases = [
+            // Different Logs before and after
+            ("before:\n```\nnsh> sleep 1\n2s\n```\nafter:\n```\nnsh> sleep 1\n1s\n```\n", ""),
+            // Placeholder from the PR Template
+            ("before:\n```\nyour testing logs here\n```\nafter:\n```\nnsh> ls\n```\n", "__Fill In The Testing Logs:__ The Testing section still says"),
+            // Empty Code Block
+            ("before:\n```\n```\nafter:\n```\nnsh> ls\n```\n", "__Fill In The Testing Logs:__ The Testing section contains an empty block"),
+            ("```\n\n```\n", "__Fill In The Testing Logs:__ The Testing section contains an empty block"),
+            // Identical Logs before and after
+            ("Testing logs before change:\n```\nnsh> ls\n```\nTesting logs after change:\n```\n nsh> ls\n```\n", "__Check The Testing Logs:__"),
+        ];
+        for (content, expected) in cases {
+            let precheck = check_testing(content, &lines);
+            match expected {
+                "" => assert_eq!(precheck, "", "{:?}", content),
+                _ => assert!(precheck.starts_with(expected), "{:?}: {:?}", content, precheck),
+            }
+        }
+    }
+
+    #[test]
+    fn code_blocks() {
+        let blocks = parse_code_blocks("Testing logs before change:\n\n```\nnsh> ls\n```\nTesting logs after change:\n```bash\n```\nunclosed:\n```\nnsh>\n");
+        assert_eq!(blocks, vec![
+            CodeBlock { caption: "Testing logs before change:".to_string(), content: "nsh> ls\n".to_string() },
+            CodeBlock { caption: "Testing logs after change:".to_string(), content: String::new() },
+        ]);
+    }
+}
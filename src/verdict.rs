@@ -0,0 +1,134 @@
+//! Structured Verdict returned by the LLM as JSON:
+//!   Pass / Fail and the Missing Items for each Section of the PR Template,
+//!   the Overall Verdict and the Confidence.
+//! We render the PR Comment from the Verdict, so the Verdict may also be used for PR Labels and Statistics.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use crate::template::SECTIONS;
+
+/// Overall Verdict for the PR
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    /// PR meets the Requirements
+    Pass,
+
+    /// PR doesn't meet the Requirements
+    Fail,
+}
+
+/// Verdict for a Section of the PR Template, like `Testing`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionVerdict {
+    /// Section Name, like `Testing`
+    pub name: String,
+
+    /// True if the Section meets the Requirements
+    pub pass: bool,
+
+    /// Items missing from the Section, like `Testing logs after change`
+    #[serde(default)]
+    pub missing: Vec<String>,
+}
+
+/// Verdict returned by the LLM
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Verdict {
+    /// Verdict for each Section of the PR Template
+    pub sections: Vec<SectionVerdict>,
+
+    /// Overall Verdict
+    pub verdict: Outcome,
+
+    /// Confidence of the LLM, from 0.0 to 1.0
+    #[serde(default)]
+    pub confidence: f64,
+}
+
+/// Return the JSON Schema of the Verdict, for the LLM Backends that support Structured Output
+pub fn schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "sections": {
+                "type": "array",
+                "description": "Verdict for each section of the PR Template",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "name":    { "type": "string", "enum": SECTIONS },
+                        "pass":    { "type": "boolean" },
+                        "missing": {
+                            "type": "array",
+                            "description": "Items that are missing from the section. Empty if the section passes.",
+                            "items": { "type": "string" }
+                        }
+                    },
+                    "required": ["name", "pass", "missing"]
+                }
+            },
+            "verdict": {
+                "type": "string",
+                "enum": ["pass", "fail"],
+                "description": "Overall verdict: pass if the PR meets the requirements"
+            },
+            "confidence": {
+                "type": "number",
+                "description": "Confidence of the verdict, from 0.0 to 1.0"
+            }
+        },
+        "required": ["sections", "verdict", "confidence"]
+    })
+}
+
+/// Parse the Verdict from the LLM Response. Code Fences around the JSON are skipped.
+pub fn parse_verdict(text: &str) -> Result<Verdict, Box<dyn std::error::Error>> {
+    // Some LLMs will wrap the JSON in ```json ... ```
+    let json = text.trim();
+    let json = json
+        .strip_prefix("```json")
+        .or(json.strip_prefix("```"))
+        .and_then(|j| j.strip_suffix("```"))
+        .unwrap_or(json);
+    let mut verdict: Verdict = serde_json::from_str(json)
+        .map_err(|e| format!("LLM returned an invalid Verdict: {}: {:?}", e, text))?;
+
+    // Some LLMs will return the Confidence as a Percentage
+    if verdict.confidence > 1.0 {
+        verdict.confidence /= 100.0;
+    }
+    verdict.confidence = verdict.confidence.clamp(0.0, 1.0);
+    Ok(verdict)
+}
+
+/// Render the Verdict as Markdown for the PR Comment, below the Prechecks.
+/// If the Verdict passes but the Prechecks don't: PR doesn't meet the Requirements until the Prechecks are fixed.
+pub fn render_verdict(verdict: &Verdict, precheck: &str) -> String {
+    // Overall Verdict and Confidence
+    let mut text = match verdict.verdict {
+        Outcome::Pass if !precheck.is_empty() => "__NuttX Requirements:__ The sections below look OK, but please fix the Prechecks above to meet the NuttX Requirements.",
+        Outcome::Pass => "__NuttX Requirements:__ This PR meets the NuttX Requirements.",
+        Outcome::Fail => "__NuttX Requirements:__ This PR doesn't meet the NuttX Requirements yet. Please check the sections below.",
+    }.to_string();
+    text.push_str(
+        &format!(" _(Confidence: {:.0}%)_\n\n", verdict.confidence * 100.0)
+    );
+
+    // Verdict for each Section: OK or the Missing Items
+    for section in verdict.sections.iter() {
+        let name = &section.name;
+        if section.pass {
+            text.push_str(&format!("__{name}:__ OK\n\n"));
+        } else if section.missing.is_empty() {
+            text.push_str(&format!("__{name}:__ Please fill in the `## {name}` section.\n\n"));
+        } else {
+            text.push_str(&format!("__{name}:__ Please add the missing items:\n"));
+            for item in section.missing.iter() {
+                text.push_str(&format!("* {item}\n"));
+            }
+            text.push('\n');
+        }
+    }
+    text
+}
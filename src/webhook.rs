@@ -0,0 +1,240 @@
+//! Webhook Server Mode: Receive the GitHub `pull_request` events and review the PRs.
+//!   Verify the `X-Hub-Signature-256` HMAC with the Webhook Secret.
+//!   Queue the PR when it's opened, edited, synchronized, labeled or ready for review.
+//!   Process the queued PRs one at a time, until SIGTERM or Ctrl-C.
+//!   PRs are reviewed again when the PR Body or Head Commit changes (needs the State Store).
+//! To test locally: POST a recorded payload to `/webhook`, signed with the Webhook Secret.
+
+use std::{collections::{HashMap, HashSet}, env, sync::{Arc, Mutex}};
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Router,
+};
+use hmac::{Hmac, Mac};
+use log::{error, info};
+use serde::Deserialize;
+use sha2::Sha256;
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    sync::mpsc,
+};
+use crate::{
+    config::Config,
+    daemon::Repo,
+    forge::{self, ForgeArgs},
+    model::ReviewModel,
+    state::StateStore,
+    Trigger,
+};
+
+/// PR Actions that will trigger a review
+const ACTIONS: [&str; 5] = ["opened", "edited", "synchronize", "labeled", "ready_for_review"];
+
+/// Queued PR: Repo (`apache/nuttx`) and PR Number
+type QueuedPr = (String, u64);
+
+/// State shared by the Webhook Handlers
+#[derive(Clone)]
+struct ServerState {
+    /// Webhook Secret from `GITHUB_WEBHOOK_SECRET`
+    secret: Arc<String>,
+
+    /// Queue of PRs to be reviewed
+    queue: mpsc::UnboundedSender<QueuedPr>,
+
+    /// PRs in the Queue, so that the same PR is queued only once
+    pending: Arc<Mutex<HashSet<QueuedPr>>>,
+}
+
+/// GitHub `pull_request` Event
+#[derive(Deserialize)]
+struct PullRequestEvent {
+    action: String,
+    number: u64,
+    pull_request: EventPull,
+    repository: EventRepo,
+}
+
+/// Pull Request in the GitHub Event
+#[derive(Deserialize)]
+struct EventPull {
+    #[serde(default)]
+    draft: bool,
+}
+
+/// Repository in the GitHub Event
+#[derive(Deserialize)]
+struct EventRepo {
+    full_name: String,
+}
+
+/// Start the Webhook Server and review the queued PRs, until SIGTERM or Ctrl-C.
+/// If Allowed Repos is empty: PRs from all Repos will be reviewed.
+pub async fn run_webhook(listen: &str, allowed: &[(String, String)], forge_args: &ForgeArgs, model: Option<&dyn ReviewModel>, state_path: Option<&str>, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    // Get the Webhook Secret. Anyone may sign the Payload with an Empty Secret.
+    let secret = env::var("GITHUB_WEBHOOK_SECRET")
+        .map_err(|_| "GITHUB_WEBHOOK_SECRET env variable is required")?;
+    if secret.trim().is_empty() {
+        return Err("GITHUB_WEBHOOK_SECRET env variable should not be empty".into());
+    }
+
+    // Start the Webhook Server in the background
+    let (queue, mut receiver) = mpsc::unbounded_channel::<QueuedPr>();
+    let pending = Arc::new(Mutex::new(HashSet::new()));
+    let app = Router::new()
+        .route("/webhook", post(handle_webhook))
+        .with_state(ServerState { secret: Arc::new(secret), queue, pending: pending.clone() });
+    let listener = tokio::net::TcpListener::bind(listen).await?;
+    info!("Webhook Server listening at http://{}/webhook", listen);
+    tokio::spawn(async move {
+        if let Err(err) = axum::serve(listener, app).await {
+            error!("Webhook Server failed: {}", err);
+        }
+    });
+
+    // Process the queued PRs one at a time. Quit on SIGTERM or Ctrl-C.
+    let mut repos: HashMap<String, Repo> = HashMap::new();
+    let mut sigterm = signal(SignalKind::terminate())?;
+    loop {
+        let (full_name, pr_id) = tokio::select! {
+            Some(queued) = receiver.recv() => queued,
+            _ = sigterm.recv() => { info!("Received SIGTERM, quitting"); return Ok(()); }
+            _ = tokio::signal::ctrl_c() => { info!("Received Ctrl-C, quitting"); return Ok(()); }
+        };
+
+        // PR may be queued again while it's reviewed
+        pending.lock().unwrap().remove(&(full_name.clone(), pr_id));
+
+        // Skip the Repos that are not allowed
+        let (owner, name) = match full_name.split_once('/') {
+            Some((owner, name)) => (owner.to_string(), name.to_string()),
+            None => continue,
+        };
+        if !allowed.is_empty() && !allowed.contains(&(owner.clone(), name.clone())) {
+            info!("Skipping PR from Repo not allowed: {}#{}", full_name, pr_id);
+            continue;
+        }
+
+        // Init the Forge, State Store and Settings for the Repo, the first time we see the Repo
+        if !repos.contains_key(&full_name) {
+            let settings = config.settings(&owner, &name, forge_args.forge);
+            let forge = match forge::new_forges(forge_args, &[(owner, name)]) {
+                Ok(mut forges) => forges.remove(0),
+                Err(err) => { error!("Failed to init Forge for {}: {}", full_name, err); continue; }
+            };
+            let state = state_path.map(|path| StateStore::new(path, &full_name));
+            repos.insert(full_name.clone(), Repo { forge, state, settings });
+        }
+        let repo = &repos[&full_name];
+
+        // Review the PR. Errors are logged and we continue with the next PR.
+        info!("Processing PR: {}#{}", full_name, pr_id);
+        if let Err(err) = crate::process_pr(repo, model, Trigger::Event, pr_id).await {
+            error!("Failed to process PR {}#{}: {}", full_name, pr_id, err);
+        }
+    }
+}
+
+/// Handle the GitHub Webhook: Verify the Signature and queue the PR
+async fn handle_webhook(State(server): State<ServerState>, headers: HeaderMap, body: Bytes) -> (StatusCode, &'static str) {
+    // Verify the Signature: `sha256=<HMAC in hex>`
+    let signature = headers
+        .get("X-Hub-Signature-256")
+        .and_then(|s| s.to_str().ok())
+        .unwrap_or("");
+    if !verify_signature(&server.secret, &body, signature) {
+        info!("Webhook: Invalid Signature");
+        return (StatusCode::UNAUTHORIZED, "Invalid Signature");
+    }
+
+    // Accept only the `pull_request` events. Reply to the `ping` event.
+    let event = headers
+        .get("X-GitHub-Event")
+        .and_then(|e| e.to_str().ok())
+        .unwrap_or("");
+    match event {
+        "pull_request" => {}
+        "ping" => return (StatusCode::OK, "pong"),
+        _ => return (StatusCode::OK, "Ignored Event"),
+    }
+
+    // Parse the Event
+    let event: PullRequestEvent = match serde_json::from_slice(&body) {
+        Ok(event) => event,
+        Err(err) => {
+            info!("Webhook: Invalid Payload: {}", err);
+            return (StatusCode::BAD_REQUEST, "Invalid Payload");
+        }
+    };
+
+    // Skip the Draft PRs and the other PR Actions
+    if !ACTIONS.contains(&event.action.as_str()) || event.pull_request.draft {
+        return (StatusCode::OK, "Ignored Action");
+    }
+
+    // Queue the PR, unless it's already in the Queue
+    let queued = (event.repository.full_name, event.number);
+    if !server.pending.lock().unwrap().insert(queued.clone()) {
+        info!("Webhook: PR already queued {}#{} ({})", queued.0, queued.1, event.action);
+        return (StatusCode::ACCEPTED, "Already Queued");
+    }
+    info!("Webhook: Queue PR {}#{} ({})", queued.0, queued.1, event.action);
+    if server.queue.send(queued).is_err() {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Queue Closed");
+    }
+    (StatusCode::ACCEPTED, "Queued")
+}
+
+/// Verify the HMAC-SHA256 Signature of the Payload: `sha256=<HMAC in hex>`. Empty Secret is never verified.
+fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    if secret.trim().is_empty() {
+        return false;
+    }
+    let signature = match signature.strip_prefix("sha256=").map(hex::decode) {
+        Some(Ok(signature)) => signature,
+        _ => return false,
+    };
+    let mut mac = match Hmac::<Sha256>::new_from_slice(secret.as_bytes()) {
+        Ok(mac) => mac,
+        Err(_) => return false,
+    };
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Secret, Payload and Signature from the GitHub Docs for Validating Webhook Deliveries
+    const SECRET: &str = "It's a Secret to Everybody";
+    const BODY: &[u8] = b"Hello, World!";
+    const SIGNATURE: &str = "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+    #[test]
+    fn signature() {
+        assert!(verify_signature(SECRET, BODY, SIGNATURE));
+
+        // Tampered Payload, Signature or Secret
+        assert!(!verify_signature(SECRET, b"Hello, World?", SIGNATURE));
+        assert!(!verify_signature(SECRET, BODY, &SIGNATURE.replace("757107", "757108")));
+        assert!(!verify_signature("Another Secret", BODY, SIGNATURE));
+
+        // Empty Secret, even if the Signature is correct
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"").unwrap();
+        mac.update(BODY);
+        let signature = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+        assert!(!verify_signature("", BODY, &signature));
+        assert!(!verify_signature(" ", BODY, SIGNATURE));
+
+        // Malformed Signature
+        assert!(!verify_signature(SECRET, BODY, SIGNATURE.trim_start_matches("sha256=")));
+        assert!(!verify_signature(SECRET, BODY, &SIGNATURE.replace("sha256=", "sha1=")));
+        assert!(!verify_signature(SECRET, BODY, "sha256=not-hex"));
+        assert!(!verify_signature(SECRET, BODY, &SIGNATURE[..20]));
+        assert!(!verify_signature(SECRET, BODY, ""));
+    }
+}